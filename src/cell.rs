@@ -1,6 +1,9 @@
+use crate::style::Align;
 use crate::unstyle::Unstyle;
+use crate::Table;
 
 /// The content of each cell of the table (either a string or a number)
+#[non_exhaustive]
 pub enum Cell<'a> {
     /// Integer variant
     Int(i32),
@@ -8,6 +11,36 @@ pub enum Cell<'a> {
     Float(f64),
     /// _Unstylable_ Text variant
     Text(Box<dyn Unstyle + 'a>),
+    /// Nested table, never cut and widened to fill its cell (see also
+    /// [`Table::set_borderless`](struct.Table.html#method.set_borderless))
+    Table(Box<Table<'a>>),
+    /// Any other variant with formatting overrides (see [`with_align`](#method.with_align),
+    /// [`with_padding`](#method.with_padding), [`with_colspan`](#method.with_colspan) and
+    /// [`with_rowspan`](#method.with_rowspan))
+    Formatted(FormattedCell<'a>),
+}
+
+/// A cell with formatting overrides, only built by the `with_*` methods of
+/// [`Cell`](enum.Cell.html) (use [`Cell::content`](enum.Cell.html#method.content) and
+/// [`Cell::format`](enum.Cell.html#method.format) to read it)
+pub struct FormattedCell<'a> {
+    cell: Box<Cell<'a>>,
+    format: CellFormat,
+}
+
+/// Formatting overrides of a single cell
+///
+/// They take precedence over the column and table settings.
+#[derive(Clone, Copy, Default)]
+pub struct CellFormat {
+    /// Alignment of the cell content
+    pub align: Option<Align>,
+    /// Number of extra spaces on each side of the cell content
+    pub padding: Option<usize>,
+    /// Number of columns spanned by the cell
+    pub colspan: Option<usize>,
+    /// Number of rows spanned by the cell
+    pub rowspan: Option<usize>,
 }
 
 impl<'a> Cell<'a> {
@@ -21,23 +54,150 @@ impl<'a> Cell<'a> {
         Self::Text(Box::new(String::from(s)))
     }
 
+    /// Overrides the alignment of this cell
+    /// # Example
+    /// ```
+    /// use stybulate::{Align, Cell};
+    /// let total = Cell::Int(42).with_align(Align::Center);
+    /// assert!(matches!(total.format().align, Some(Align::Center)));
+    /// ```
+    pub fn with_align(self, align: Align) -> Self {
+        self.with_format(|format| format.align = Some(align))
+    }
+
+    /// Adds `padding` spaces on each side of the content of this cell
+    pub fn with_padding(self, padding: usize) -> Self {
+        self.with_format(|format| format.padding = Some(padding))
+    }
+
+    /// Makes this cell span `colspan` columns
+    ///
+    /// The following cells of the row are shifted accordingly.
+    /// # Example
+    /// ```
+    /// use stybulate::{Cell, Headers, Style, Table};
+    /// let table = Table::new(
+    ///     Style::Grid,
+    ///     vec![
+    ///         vec![Cell::from("eggs"), Cell::Int(451), Cell::Int(12)],
+    ///         vec![Cell::from("no data for spam").with_colspan(3)],
+    ///     ],
+    ///     None,
+    /// );
+    /// let expected = [
+    ///     "+------+-----+-----+",
+    ///     "| eggs | 451 |  12 |",
+    ///     "+------+-----+-----+",
+    ///     "| no data for spam |",
+    ///     "+------------------+",
+    /// ].join("\n");
+    /// assert_eq!(expected, table.tabulate());
+    /// ```
+    pub fn with_colspan(self, colspan: usize) -> Self {
+        self.with_format(|format| format.colspan = Some(colspan))
+    }
+
+    /// Makes this cell span `rowspan` rows
+    ///
+    /// The following rows have no cell in the spanned columns: their cells are shifted to the
+    /// next free columns. No line is drawn between the spanned rows in these columns.
+    /// # Example
+    /// ```
+    /// use stybulate::{Cell, Style, Table};
+    /// let table = Table::new(
+    ///     Style::Grid,
+    ///     vec![
+    ///         vec![Cell::from("web").with_rowspan(2), Cell::from("http")],
+    ///         vec![Cell::from("https")],
+    ///     ],
+    ///     None,
+    /// );
+    /// let expected = [
+    ///     "+-----+-------+",
+    ///     "| web | http  |",
+    ///     "|     +-------+",
+    ///     "|     | https |",
+    ///     "+-----+-------+",
+    /// ].join("\n");
+    /// assert_eq!(expected, table.tabulate());
+    /// ```
+    pub fn with_rowspan(self, rowspan: usize) -> Self {
+        self.with_format(|format| format.rowspan = Some(rowspan))
+    }
+
+    fn with_format<F: FnOnce(&mut CellFormat)>(self, f: F) -> Self {
+        match self {
+            Self::Formatted(mut formatted) => {
+                f(&mut formatted.format);
+                Self::Formatted(formatted)
+            }
+            cell => {
+                let mut format = CellFormat::default();
+                f(&mut format);
+                Self::Formatted(FormattedCell {
+                    cell: Box::new(cell),
+                    format,
+                })
+            }
+        }
+    }
+
+    /// Returns the cell with its column and row spans swapped
+    pub(crate) fn transposed(self) -> Self {
+        match self {
+            Self::Formatted(FormattedCell { cell, format }) => Self::Formatted(FormattedCell {
+                cell,
+                format: CellFormat {
+                    colspan: format.rowspan,
+                    rowspan: format.colspan,
+                    ..format
+                },
+            }),
+            cell => cell,
+        }
+    }
+
+    /// Returns the formatting overrides of this cell (all `None` if there are none)
+    pub fn format(&self) -> CellFormat {
+        match self {
+            Self::Formatted(formatted) => formatted.format,
+            _ => CellFormat::default(),
+        }
+    }
+
+    /// Returns the cell without its formatting overrides
+    pub fn content(&self) -> &Self {
+        match self {
+            Self::Formatted(formatted) => &formatted.cell,
+            cell => cell,
+        }
+    }
+
     /// Is it a number ?
     pub fn is_a_number(&self) -> bool {
-        matches!(self, Self::Int(_) | Self::Float(_))
+        matches!(self.content(), Self::Int(_) | Self::Float(_))
     }
 
     /// Returns the unstylable content if it is a Text Variant, None otherwise
     #[allow(clippy::borrowed_box)]
     pub fn to_unstylable(&self) -> Option<&Box<dyn Unstyle + 'a>> {
-        match self {
+        match self.content() {
             Self::Text(s) => Some(s),
             _ => None,
         }
     }
 
-    /// Returns the string representation of a number, None if it is a Text Variant
+    /// Returns the nested table if it is a Table Variant, None otherwise
+    pub fn to_table(&self) -> Option<&Table<'a>> {
+        match self.content() {
+            Self::Table(table) => Some(table),
+            _ => None,
+        }
+    }
+
+    /// Returns the string representation of a number, None otherwise
     pub fn to_string(&self) -> Option<String> {
-        match self {
+        match self.content() {
             Self::Int(i) => Some(i.to_string()),
             Self::Float(f) => Some(f.to_string()),
             _ => None,
@@ -46,7 +206,7 @@ impl<'a> Cell<'a> {
 
     /// Same as [`to_string`](#method.to_string) but formatted with a precision
     pub fn to_string_with_precision(&self, digits: usize) -> Option<String> {
-        match self {
+        match self.content() {
             Self::Int(i) => Some(format!("{:.prec$}", *i as f64, prec = digits)),
             Self::Float(f) => Some(format!("{:.prec$}", f, prec = digits)),
             _ => None,
@@ -55,7 +215,7 @@ impl<'a> Cell<'a> {
 
     /// Number of digits after the dot in a float, 0 otherwise
     pub fn digits_len(&self) -> usize {
-        if let Self::Float(f) = self {
+        if let Self::Float(f) = self.content() {
             let s = f.to_string();
             if let Some(pos) = s.find('.') {
                 s.len() - (pos + 1)
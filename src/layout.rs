@@ -0,0 +1,95 @@
+use std::cmp;
+use std::mem;
+
+use crate::{RenderOptions, Table};
+
+/// Column widths shared by several tables, so that their columns line up
+/// # Example
+/// ```
+/// use stybulate::{Table, Style, Cell, Headers, Layout};
+/// let north = Table::new(
+///     Style::Simple,
+///     vec![vec![Cell::from("Oslo"), Cell::Int(709)]],
+///     Some(Headers::from(vec!["city", "population"])),
+/// );
+/// let south = Table::new(
+///     Style::Simple,
+///     vec![vec![Cell::from("Cape Town"), Cell::Int(4772)]],
+///     Some(Headers::from(vec!["city", "pop."])),
+/// );
+/// let layout = Layout::new(&[&north, &south]);
+/// let expected = [
+///     "city         population",
+///     "---------  ------------",
+///     "Oslo                709",
+/// ].join("\n");
+/// assert_eq!(expected, north.tabulate_with_layout(&layout));
+/// let expected = [
+///     "city               pop.",
+///     "---------  ------------",
+///     "Cape Town          4772",
+/// ].join("\n");
+/// assert_eq!(expected, south.tabulate_with_layout(&layout));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Layout {
+    col_width: Vec<usize>,
+}
+
+impl Layout {
+    /// Joint layout of the tables: each column is as wide as in the widest of them
+    pub fn new(tables: &[&Table]) -> Self {
+        let mut layout = Self::default();
+        for table in tables {
+            let (_, mut other) = table.render(&RenderOptions::default());
+            // the layout keeps the columns of the table with the most of them
+            if layout.col_width.len() < other.col_width.len() {
+                mem::swap(&mut layout, &mut other);
+            }
+            other.widen(&mut layout.col_width);
+        }
+        layout
+    }
+
+    /// Widths of the columns, without their separators
+    pub fn widths(&self) -> &[usize] {
+        &self.col_width
+    }
+
+    /// Widens the columns to the ones of the layout
+    pub(crate) fn widen(&self, col_width: &mut [usize]) {
+        for (w, layout) in col_width.iter_mut().zip(&self.col_width) {
+            *w = cmp::max(*w, *layout);
+        }
+    }
+}
+
+/// Layout with the given column widths
+impl From<Vec<usize>> for Layout {
+    fn from(col_width: Vec<usize>) -> Self {
+        Self { col_width }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cell, Style};
+
+    #[test]
+    fn new_keeps_widest_columns() {
+        let narrow = Table::new(
+            Style::Plain,
+            vec![vec![Cell::from("a"), Cell::from("bbbb"), Cell::from("c")]],
+            None,
+        );
+        let wide = Table::new(
+            Style::Plain,
+            vec![vec![Cell::from("aaa"), Cell::from("b")]],
+            None,
+        );
+        assert_eq!(&[3, 4, 1], Layout::new(&[&narrow, &wide]).widths());
+        assert_eq!(&[3, 4, 1], Layout::new(&[&wide, &narrow]).widths());
+        assert_eq!(Layout::default(), Layout::new(&[]));
+    }
+}
@@ -15,7 +15,7 @@
 //!     ],
 //!     Some(Headers::from(vec!["strings", "numbers"])),
 //! ).tabulate();
-//! let expected = vec![
+//! let expected = [
 //!     "╒═══════════╤═══════════╕",
 //!     "│ strings   │   numbers │",
 //!     "╞═══════════╪═══════════╡",
@@ -31,25 +31,42 @@
 
 use std::cmp;
 use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::iter;
+use std::mem;
+use std::ops::Range;
+use std::slice;
 
 use unicode_width::UnicodeWidthStr;
 
 mod style;
-pub use style::{Align, Style};
+pub use style::{Align, Fit, Index, Margin, Overflow, PageSize, Style, Width};
 
 mod unstyle;
 pub use unstyle::{AsciiEscapedString, Unstyle};
 
 mod cell;
-pub use cell::Cell;
+pub use cell::{Cell, CellFormat, FormattedCell};
+
+mod layout;
+pub use layout::Layout;
+
+mod summary;
+pub use summary::Agg;
+
+mod stack;
+pub use stack::{hstack, vstack};
+
+mod wrap;
 
 // constants
 const MIN_PADDING: usize = 2;
 
-/// The Headers structure is a list of headers (per column)
+/// The Headers structure is a list of headers (per column), possibly on several levels
 /// # Example
 /// ```
-/// use stybulate::{AsciiEscapedString, Headers};
+/// use stybulate::{Align, AsciiEscapedString, Cell, Headers};
 /// // simple example with only strings
 /// let simple = Headers::from(vec!["foo", "bar"]);
 /// // more elaborated example with a mix of a styled string and a simple string
@@ -57,10 +74,28 @@ const MIN_PADDING: usize = 2;
 /// with_style
 ///     .push(AsciiEscapedString::from("\x1b[1;35mfoo\x1b[0m bar"))
 ///     .push(String::from("baz"));
+/// // a header spanning several columns
+/// let mut grouped = Headers::new();
+/// grouped.push(String::from("host")).push_span(String::from("Latency (ms)"), 3);
+/// // two levels of headers: groups above the column names
+/// let mut levels = Headers::new();
+/// levels
+///     .push_cell(Cell::from("host").with_rowspan(2))
+///     .push_span(String::from("disk"), 2)
+///     .set_level_align(Align::Center);
+/// levels.add_level().push(String::from("read")).push(String::from("write"));
 /// ```
-#[derive(Default)]
 pub struct Headers {
-    headers: Vec<Box<dyn Unstyle>>,
+    /// header rows, from top to bottom
+    levels: Vec<Vec<Cell<'static>>>,
+    /// alignment of each header row
+    aligns: Vec<Option<Align>>,
+}
+
+impl Default for Headers {
+    fn default() -> Self {
+        Self::with_capacity(0)
+    }
 }
 
 impl Headers {
@@ -71,40 +106,49 @@ impl Headers {
 
     /// Headers constructor from a vec of `&str`
     pub fn from(headers: Vec<&str>) -> Self {
-        let mut unstyle_headers: Vec<Box<dyn Unstyle>> = Vec::with_capacity(headers.len());
-        for header in headers.iter() {
-            unstyle_headers.push(Box::new(String::from(*header)));
-        }
         Self {
-            headers: unstyle_headers,
+            levels: vec![headers.into_iter().map(Cell::from).collect()],
+            aligns: vec![None],
         }
     }
 
     /// Headers constructor with capacity
     pub fn with_capacity(capacity: usize) -> Self {
         Self {
-            headers: Vec::with_capacity(capacity),
+            levels: vec![Vec::with_capacity(capacity)],
+            aligns: vec![None],
         }
     }
 
     /// Add a header to the Headers
     pub fn push<H: Unstyle + 'static>(&mut self, header: H) -> &mut Self {
-        self.headers.push(Box::new(header));
-        self
+        self.push_cell(Cell::Text(Box::new(header)))
+    }
+
+    /// Add a header spanning `span` columns to the Headers
+    pub fn push_span<H: Unstyle + 'static>(&mut self, header: H, span: usize) -> &mut Self {
+        self.push_cell(Cell::Text(Box::new(header)).with_colspan(span))
     }
 
-    fn len(&self) -> usize {
-        self.headers.len()
+    /// Add a header cell to the Headers, with its formatting overrides
+    ///
+    /// A cell spanning several rows covers the same columns in the next levels.
+    pub fn push_cell(&mut self, header: Cell<'static>) -> &mut Self {
+        self.levels.last_mut().unwrap().push(header);
+        self
     }
 
-    #[allow(clippy::borrowed_box)]
-    fn get(&self, i: usize) -> Option<&Box<dyn Unstyle>> {
-        self.headers.get(i)
+    /// Starts a new row of headers below the current ones: the next headers are added to it
+    pub fn add_level(&mut self) -> &mut Self {
+        self.levels.push(Vec::new());
+        self.aligns.push(None);
+        self
     }
 
-    #[allow(clippy::borrowed_box)]
-    fn to_ref_vec(&self) -> Vec<&Box<dyn Unstyle>> {
-        self.headers.iter().collect()
+    /// Sets the alignment of the current row of headers (the cell overrides take precedence)
+    pub fn set_level_align(&mut self, align: Align) -> &mut Self {
+        *self.aligns.last_mut().unwrap() = Some(align);
+        self
     }
 }
 
@@ -128,9 +172,39 @@ pub struct Table<'a> {
     num_align: Align,
     contents: Vec<Vec<Cell<'a>>>,
     headers: Option<Headers>,
+    columns: HashMap<usize, ColumnFormat>,
+    max_width: Option<usize>,
+    overflow: Overflow,
+    truncation_marker: String,
+    fit: Fit,
+    key_cols: usize,
+    expanded: bool,
+    hidden_note: bool,
+    borderless: bool,
+    header_lines: bool,
+    header_repeat: Option<PageSize>,
+    margin: Option<Margin>,
+    pad_lines: bool,
+    /// first row and heading of each section
+    sections: Vec<(usize, Option<String>)>,
+    footers: Vec<Vec<Cell<'a>>>,
+    summary: Vec<Agg>,
+    title: Option<String>,
+    caption: Option<(String, Align)>,
+    index: Option<Index>,
+    index_header: String,
+    index_align: Option<Align>,
+    index_rule: bool,
+    stub: bool,
+    /// depth of each row of the contents in a tree
+    depths: Vec<usize>,
 
     #[cfg(feature = "ansi_term_style")]
     border_style: Option<ansi_term::Style>,
+    #[cfg(feature = "ansi_term_style")]
+    section_style: Option<ansi_term::Style>,
+    #[cfg(feature = "ansi_term_style")]
+    stub_style: Option<ansi_term::Style>,
 }
 
 impl<'a> Table<'a> {
@@ -142,11 +216,93 @@ impl<'a> Table<'a> {
             num_align: Align::Decimal,
             #[cfg(feature = "ansi_term_style")]
             border_style: None,
+            #[cfg(feature = "ansi_term_style")]
+            section_style: None,
+            #[cfg(feature = "ansi_term_style")]
+            stub_style: None,
             contents,
             headers,
+            columns: HashMap::new(),
+            max_width: None,
+            overflow: Overflow::Wrap,
+            truncation_marker: String::from("…"),
+            fit: Fit::Shrink,
+            key_cols: 0,
+            expanded: false,
+            hidden_note: false,
+            borderless: false,
+            header_lines: true,
+            header_repeat: None,
+            margin: None,
+            pad_lines: false,
+            sections: Vec::new(),
+            footers: Vec::new(),
+            summary: Vec::new(),
+            title: None,
+            caption: None,
+            index: None,
+            index_header: String::new(),
+            index_align: None,
+            index_rule: false,
+            stub: false,
+            depths: Vec::new(),
         }
     }
 
+    /// Two-column table of keys and values, the keys being the stub column (see
+    /// [`set_stub`](#method.set_stub)), in the order of `pairs`
+    ///
+    /// Keys and values are displayed as texts, which may be styled with ASCII escape sequences.
+    /// # Example
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use stybulate::{Table, Style, Headers};
+    /// let mut config = BTreeMap::new();
+    /// config.insert("port", 8080);
+    /// config.insert("host", 0);
+    /// let table = Table::from_pairs(Style::Grid, config, Some(Headers::from(vec!["key", "value"])));
+    /// let expected = [
+    ///     "+-------++---------+",
+    ///     "| key   || value   |",
+    ///     "+=======++=========+",
+    ///     "| host  || 0       |",
+    ///     "+-------++---------+",
+    ///     "| port  || 8080    |",
+    ///     "+-------++---------+",
+    /// ].join("\n");
+    /// assert_eq!(expected, table.tabulate());
+    /// ```
+    pub fn from_pairs<I, K, V>(style: Style, pairs: I, headers: Option<Headers>) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: fmt::Display,
+        V: fmt::Display,
+    {
+        let text = |d: &dyn fmt::Display| -> Cell<'a> {
+            Cell::Text(Box::new(AsciiEscapedString::from(&d.to_string())))
+        };
+        let contents = pairs
+            .into_iter()
+            .map(|(key, value)| vec![text(&key), text(&value)])
+            .collect();
+        let mut table = Self::new(style, contents, headers);
+        table.set_stub(true);
+        table
+    }
+
+    /// Same as [`from_pairs`](#method.from_pairs), the pairs being sorted by key (as for a
+    /// `HashMap`, whose order is arbitrary)
+    pub fn from_sorted_pairs<I, K, V>(style: Style, pairs: I, headers: Option<Headers>) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: fmt::Display + Ord,
+        V: fmt::Display,
+    {
+        let mut pairs: Vec<(K, V)> = pairs.into_iter().collect();
+        pairs.sort_by(|a, b| a.0.cmp(&b.0));
+        Self::from_pairs(style, pairs, headers)
+    }
+
     /// Set the table alignments (defaults are `Align::Left` for strings and `Align::Decimal` for numbers)
     /// # Panics
     /// Panics if str_align is equal to `Align::Decimal`
@@ -158,6 +314,44 @@ impl<'a> Table<'a> {
         self.num_align = num_align;
     }
 
+    /// Set the alignment of a column (header included), overriding the table alignments
+    ///
+    /// `Align::Decimal` only makes sense for numbers: text cells of the column are then right
+    /// aligned.
+    pub fn set_col_align(&mut self, col: usize, align: Align) {
+        self.columns.entry(col).or_default().align = Some(align);
+    }
+
+    /// Set the maximum width of the columns
+    ///
+    /// Longer texts (headers included) are wrapped or truncated depending on the
+    /// [`Overflow`](enum.Overflow.html) policy. Numbers are never cut.
+    pub fn set_max_width(&mut self, width: usize) {
+        self.max_width = Some(width);
+    }
+
+    /// Set the maximum width of a column, overriding the one set with
+    /// [`set_max_width`](#method.set_max_width)
+    pub fn set_col_max_width(&mut self, col: usize, width: usize) {
+        self.columns.entry(col).or_default().max_width = Some(width);
+    }
+
+    /// Set how texts wider than their column max width are fitted (default is `Overflow::Wrap`)
+    pub fn set_overflow(&mut self, overflow: Overflow) {
+        self.overflow = overflow;
+    }
+
+    /// Set how texts wider than the max width of a column are fitted, overriding the policy set
+    /// with [`set_overflow`](#method.set_overflow)
+    pub fn set_col_overflow(&mut self, col: usize, overflow: Overflow) {
+        self.columns.entry(col).or_default().overflow = Some(overflow);
+    }
+
+    /// Set the marker ending truncated lines (default is `…`)
+    pub fn set_truncation_marker(&mut self, marker: &str) {
+        self.truncation_marker = String::from(marker);
+    }
+
     #[cfg(feature = "ansi_term_style")]
     /// Set the borders style
     /// # Feature
@@ -166,1100 +360,4763 @@ impl<'a> Table<'a> {
         self.border_style = Some(style);
     }
 
+    #[cfg(feature = "ansi_term_style")]
+    /// Set the style of the section headings
+    /// # Feature
+    /// Needs feature `ansi_term_style`.
+    pub fn set_section_style(&mut self, style: ansi_term::Style) {
+        self.section_style = Some(style);
+    }
+
+    #[cfg(feature = "ansi_term_style")]
+    /// Set the style of the cells of the stub column (see [`set_stub`](#method.set_stub))
+    /// # Feature
+    /// Needs feature `ansi_term_style`.
+    pub fn set_stub_style(&mut self, style: ansi_term::Style) {
+        self.stub_style = Some(style);
+    }
+
+    /// Set the width of a column (see [`Width`](enum.Width.html)), e.g. to align the columns of
+    /// several tables
+    ///
+    /// [`tabulate_to_width`](#method.tabulate_to_width) never shrinks the column below it.
+    /// # Example
+    /// ```
+    /// use stybulate::{Table, Style, Cell, Width};
+    /// let mut table = Table::new(
+    ///     Style::Grid,
+    ///     vec![vec![Cell::from("spam"), Cell::from("a long description")]],
+    ///     None,
+    /// );
+    /// table.set_col_width(0, Width::AtLeast(8));
+    /// table.set_col_width(1, Width::Exactly(10));
+    /// let expected = [
+    ///     "+----------+------------+",
+    ///     "| spam     | a long     |",
+    ///     "|          | descriptio |",
+    ///     "|          | n          |",
+    ///     "+----------+------------+",
+    /// ].join("\n");
+    /// assert_eq!(expected, table.tabulate());
+    /// ```
+    pub fn set_col_width(&mut self, col: usize, width: Width) {
+        self.columns.entry(col).or_default().width = Some(width);
+    }
+
+    /// Set the minimum width a column can be shrunk to by
+    /// [`tabulate_to_width`](#method.tabulate_to_width)
+    pub fn set_col_min_width(&mut self, col: usize, width: usize) {
+        self.columns.entry(col).or_default().min_width = Some(width);
+    }
+
+    /// Set the priority of a column (default is 0)
+    ///
+    /// When the table is too wide, columns with the lowest priority are shrunk (or hidden) first.
+    pub fn set_col_priority(&mut self, col: usize, priority: i32) {
+        self.columns.entry(col).or_default().priority = priority;
+    }
+
+    /// Set how [`tabulate_to_width`](#method.tabulate_to_width) fits a table too wide (default
+    /// is `Fit::Shrink`)
+    pub fn set_fit(&mut self, fit: Fit) {
+        self.fit = fit;
+    }
+
+    /// Set the number of key columns, the first ones of the contents, repeated in each block of
+    /// columns with [`Fit::Split`](enum.Fit.html#variant.Split) (the index and stub columns are
+    /// always repeated)
+    /// # Example
+    /// ```
+    /// use stybulate::{Table, Style, Cell, Headers, Fit};
+    /// let mut table = Table::new(
+    ///     Style::Simple,
+    ///     vec![vec![Cell::from("spam"), Cell::Int(42), Cell::Int(7), Cell::Float(1.5)]],
+    ///     Some(Headers::from(vec!["item", "quantity", "remaining", "price"])),
+    /// );
+    /// table.set_fit(Fit::Split);
+    /// table.set_key_cols(1);
+    /// let expected = [
+    ///     "item      quantity    remaining",
+    ///     "------  ----------  -----------",
+    ///     "spam            42            7",
+    ///     "",
+    ///     "item      price",
+    ///     "------  -------",
+    ///     "spam        1.5",
+    /// ].join("\n");
+    /// assert_eq!(expected, table.tabulate_to_width(32));
+    /// ```
+    pub fn set_key_cols(&mut self, key_cols: usize) {
+        self.key_cols = key_cols;
+    }
+
+    /// Repeat the headers (and the line below them) after each group of rows of the given size
+    pub fn set_header_repeat(&mut self, size: PageSize) {
+        self.header_repeat = Some(size);
+    }
+
+    /// Append a line like "3 columns hidden" below the table when columns were hidden to fit
+    /// it (see [`Fit::HideColumns`](enum.Fit.html#variant.HideColumns))
+    pub fn set_hidden_note(&mut self, hidden_note: bool) {
+        self.hidden_note = hidden_note;
+    }
+
+    /// Print each row as a record of `header | value` lines, the records being separated by
+    /// lines like `-[ RECORD 1 ]-` (default is false)
+    ///
+    /// The label of each column is made of its headers of all the levels. Only the contents
+    /// are printed: there is no index, section, footer, summary, title or caption.
+    /// # Example
+    /// ```
+    /// use stybulate::{Table, Style, Cell, Headers};
+    /// let mut table = Table::new(
+    ///     Style::Presto,
+    ///     vec![vec![Cell::from("spam"), Cell::Int(42)], vec![Cell::from("eggs"), Cell::Int(451)]],
+    ///     Some(Headers::from(vec!["item", "qty"])),
+    /// );
+    /// table.set_expanded(true);
+    /// let expected = [
+    ///     "-[ RECORD 1 ]--",
+    ///     " item | spam",
+    ///     " qty  | 42",
+    ///     "-[ RECORD 2 ]--",
+    ///     " item | eggs",
+    ///     " qty  | 451",
+    /// ].join("\n");
+    /// assert_eq!(expected, table.tabulate());
+    /// ```
+    pub fn set_expanded(&mut self, expanded: bool) {
+        self.expanded = expanded;
+    }
+
+    /// Indent the table, or center it in a width (its lines being indented by half of the
+    /// remaining width)
+    ///
+    /// A left margin is taken from the width the table is fitted in
+    /// ([`tabulate_to_width`](#method.tabulate_to_width)).
+    /// # Example
+    /// ```
+    /// use stybulate::{Table, Style, Cell, Headers, Margin};
+    /// let mut table = Table::new(
+    ///     Style::Grid,
+    ///     vec![vec![Cell::from("spam"), Cell::Int(42)]],
+    ///     Some(Headers::from(vec!["item", "qty"])),
+    /// );
+    /// table.set_margin(Margin::Center(30));
+    /// let expected = [
+    ///     "      +--------+-------+",
+    ///     "      | item   |   qty |",
+    ///     "      +========+=======+",
+    ///     "      | spam   |    42 |",
+    ///     "      +--------+-------+",
+    /// ].join("\n");
+    /// assert_eq!(expected, table.tabulate());
+    /// ```
+    pub fn set_margin(&mut self, margin: Margin) {
+        self.margin = Some(margin);
+    }
+
+    /// Pad every line with spaces to the width of the table, instead of trimming the spaces
+    /// at its end (default is false)
+    pub fn set_pad_lines(&mut self, pad_lines: bool) {
+        self.pad_lines = pad_lines;
+    }
+
+    /// Draw the table without its outer borders (default is false), e.g. to blend it in the cell
+    /// of another table (see [`Cell::Table`](enum.Cell.html#variant.Table))
+    /// # Example
+    /// ```
+    /// use stybulate::{Table, Style, Cell, Headers};
+    /// let mut inner = Table::new(
+    ///     Style::Fancy,
+    ///     vec![vec![Cell::from("cpu"), Cell::Int(2)], vec![Cell::from("mem"), Cell::Int(512)]],
+    ///     None,
+    /// );
+    /// inner.set_borderless(true);
+    /// let table = Table::new(
+    ///     Style::Fancy,
+    ///     vec![vec![Cell::from("web"), Cell::Table(Box::new(inner))]],
+    ///     Some(Headers::from(vec!["service", "resources limits"])),
+    /// );
+    /// let expected = [
+    ///     "╒═══════════╤════════════════════╕",
+    ///     "│ service   │ resources limits   │",
+    ///     "╞═══════════╪════════════════════╡",
+    ///     "│ web       │ cpu     │        2 │",
+    ///     "│           │ ────────┼───────── │",
+    ///     "│           │ mem     │      512 │",
+    ///     "╘═══════════╧════════════════════╛",
+    /// ].join("\n");
+    /// assert_eq!(expected, table.tabulate());
+    /// ```
+    pub fn set_borderless(&mut self, borderless: bool) {
+        self.borderless = borderless;
+    }
+
+    /// Draw a line between the levels of headers, if the style has one (default is true)
+    pub fn set_header_lines(&mut self, header_lines: bool) {
+        self.header_lines = header_lines;
+    }
+
+    /// Add a row to the contents
+    pub fn push_row(&mut self, row: Vec<Cell<'a>>) {
+        self.contents.push(row);
+    }
+
+    /// Start a new section: the next rows are preceded by a line and a heading spanning all
+    /// the columns
+    ///
+    /// Cells cannot span rows of different sections.
+    /// # Example
+    /// ```
+    /// use stybulate::{Cell, Headers, Style, Table};
+    /// let mut table = Table::new(Style::Simple, vec![], Some(Headers::from(vec!["host", "cpu"])));
+    /// table.push_section("production");
+    /// table.push_row(vec![Cell::from("web-01"), Cell::Int(12)]);
+    /// table.push_row(vec![Cell::from("db-01"), Cell::Int(71)]);
+    /// table.push_section("staging");
+    /// table.push_row(vec![Cell::from("web-02"), Cell::Int(3)]);
+    /// let expected = [
+    ///     "host      cpu",
+    ///     "------  -----",
+    ///     "production",
+    ///     "web-01     12",
+    ///     "db-01      71",
+    ///     "------  -----",
+    ///     "staging",
+    ///     "web-02      3",
+    /// ].join("\n");
+    /// assert_eq!(expected, table.tabulate());
+    /// ```
+    pub fn push_section(&mut self, heading: &str) {
+        self.sections
+            .push((self.contents.len(), Some(String::from(heading))));
+    }
+
+    /// Start a new section without heading: the next rows are only preceded by a line
+    pub fn push_separator(&mut self) {
+        self.sections.push((self.contents.len(), None));
+    }
+
+    /// Add a footer row (e.g. totals), rendered below the contents after a dedicated line
+    ///
+    /// Numbers in footers share the precision of their column.
+    pub fn push_footer(&mut self, footer: Vec<Cell<'a>>) {
+        self.footers.push(footer);
+    }
+
+    /// Add summary rows below the footers, one per aggregate
+    ///
+    /// The aggregates are computed over the numbers of each numeric column, with its precision
+    /// (at least one decimal with `Agg::Mean`), and labelled in the first text column after the
+    /// index.
+    /// # Example
+    /// ```
+    /// use stybulate::{Agg, Cell, Style, Table};
+    /// let mut table = Table::new(
+    ///     Style::Simple,
+    ///     vec![
+    ///         vec![Cell::from("spam"), Cell::Float(41.5)],
+    ///         vec![Cell::from("eggs"), Cell::Int(451)],
+    ///     ],
+    ///     None,
+    /// );
+    /// table.add_summary(&[Agg::Sum, Agg::Max]);
+    /// let expected = [
+    ///     "----  -----",
+    ///     "spam   41.5",
+    ///     "eggs  451",
+    ///     "----  -----",
+    ///     "sum   492.5",
+    ///     "max   451",
+    ///     "----  -----",
+    /// ].join("\n");
+    /// assert_eq!(expected, table.tabulate());
+    /// ```
+    pub fn add_summary(&mut self, aggs: &[Agg]) {
+        self.summary.extend_from_slice(aggs);
+    }
+
+    /// Set a title, embedded in the line above the table or centered above it if the style has
+    /// no such line
+    ///
+    /// The columns are widened if the title is wider than them.
+    pub fn set_title(&mut self, title: &str) {
+        self.title = Some(String::from(title));
+    }
+
+    /// Set a caption below the table, wrapped to the table width
+    /// # Panics
+    /// Panics if align is equal to `Align::Decimal`
+    pub fn set_caption(&mut self, caption: &str, align: Align) {
+        if align == Align::Decimal {
+            panic!("a caption cannot be aligned with Decimal");
+        }
+        self.caption = Some((String::from(caption), align));
+    }
+
+    /// Prepend an index column labelling the content rows
+    ///
+    /// The column settings (`set_col_*`) keep applying to the columns of the contents.
+    /// # Example
+    /// ```
+    /// use stybulate::{Table, Style, Cell, Headers, Index};
+    /// let mut table = Table::new(
+    ///     Style::Simple,
+    ///     vec![vec![Cell::from("spam"), Cell::Int(42)], vec![Cell::from("eggs"), Cell::Int(451)]],
+    ///     Some(Headers::from(vec!["item", "qty"])),
+    /// );
+    /// table.set_index(Index::FromOne);
+    /// table.set_index_header("#");
+    /// let expected = [
+    ///     "  #  item      qty",
+    ///     "---  ------  -----",
+    ///     "  1  spam       42",
+    ///     "  2  eggs      451",
+    /// ].join("\n");
+    /// assert_eq!(expected, table.tabulate());
+    /// ```
+    pub fn set_index(&mut self, index: Index) {
+        self.index = Some(index);
+    }
+
+    /// Set the header of the index column (empty by default)
+    pub fn set_index_header(&mut self, header: &str) {
+        self.index_header = String::from(header);
+    }
+
+    /// Set the alignment of the index column, overriding the table alignments
+    pub fn set_index_align(&mut self, align: Align) {
+        self.index_align = Some(align);
+    }
+
+    /// Draw a vertical rule between the index column and the contents (default is false)
+    pub fn set_index_rule(&mut self, rule: bool) {
+        self.index_rule = rule;
+    }
+
+    /// Treat the first column of the contents as a stub of row headers: it is aligned as text
+    /// (never numerically) and followed by a vertical rule (default is false)
+    ///
+    /// The rule is stronger than the other column separators in all the styles but
+    /// `Style::Github`, Markdown tables having a single kind of separator.
+    /// # Example
+    /// ```
+    /// use stybulate::{Table, Style, Cell, Headers};
+    /// let mut table = Table::new(
+    ///     Style::Fancy,
+    ///     vec![vec![Cell::Int(2020), Cell::Float(1.5)], vec![Cell::Int(2021), Cell::Float(12.25)]],
+    ///     Some(Headers::from(vec!["year", "growth"])),
+    /// );
+    /// table.set_stub(true);
+    /// let expected = [
+    ///     "╒════════╦══════════╕",
+    ///     "│ year   ║   growth │",
+    ///     "╞════════╬══════════╡",
+    ///     "│ 2020   ║     1.5  │",
+    ///     "├────────╫──────────┤",
+    ///     "│ 2021   ║    12.25 │",
+    ///     "╘════════╩══════════╛",
+    /// ].join("\n");
+    /// assert_eq!(expected, table.tabulate());
+    /// ```
+    pub fn set_stub(&mut self, stub: bool) {
+        self.stub = stub;
+    }
+
+    /// Draw the rows as a tree, each one being at the given depth (the roots being at 0, like
+    /// the rows without depth): the first column of the contents is prefixed with `├─`, `└─`
+    /// and `│` guides
+    ///
+    /// A row is the child of the row above it at a smaller depth.
+    /// # Example
+    /// ```
+    /// use stybulate::{Table, Style, Cell, Headers};
+    /// let mut table = Table::new(
+    ///     Style::Plain,
+    ///     vec![
+    ///         vec![Cell::from("src"), Cell::Int(120)],
+    ///         vec![Cell::from("lib.rs"), Cell::Int(100)],
+    ///         vec![Cell::from("main.rs"), Cell::Int(20)],
+    ///         vec![Cell::from("README.md"), Cell::Int(5)],
+    ///     ],
+    ///     Some(Headers::from(vec!["path", "KiB"])),
+    /// );
+    /// table.set_depths(vec![0, 1, 1]);
+    /// let expected = [
+    ///     "path          KiB",
+    ///     "src           120",
+    ///     "├─ lib.rs     100",
+    ///     "└─ main.rs     20",
+    ///     "README.md       5",
+    /// ].join("\n");
+    /// assert_eq!(expected, table.tabulate());
+    /// ```
+    pub fn set_depths(&mut self, depths: Vec<usize>) {
+        self.depths = depths;
+    }
+
+    /// Transposes the table: the levels of headers become the first columns and each row a
+    /// column
+    ///
+    /// Cells keep their type, so that each new column of numbers is still aligned as such, and
+    /// their column and row spans are swapped. Footers become the last columns, while sections
+    /// and the column settings (`set_col_*`) are dropped.
+    /// # Example
+    /// ```
+    /// use stybulate::{Table, Style, Cell, Headers};
+    /// let mut table = Table::new(
+    ///     Style::Simple,
+    ///     vec![vec![Cell::Int(2020), Cell::Float(1.5)], vec![Cell::Int(2021), Cell::Float(12.25)]],
+    ///     Some(Headers::from(vec!["year", "growth"])),
+    /// );
+    /// table.transpose();
+    /// let expected = [
+    ///     "------  ------  -------",
+    ///     "year    2020    2021",
+    ///     "growth     1.5    12.25",
+    ///     "------  ------  -------",
+    /// ].join("\n");
+    /// assert_eq!(expected, table.tabulate());
+    /// ```
+    pub fn transpose(&mut self) {
+        let mut rows: Vec<Vec<Cell<'a>>> = Vec::new();
+        if let Some(headers) = self.headers.take() {
+            rows.extend(headers.levels);
+        }
+        rows.append(&mut self.contents);
+        rows.append(&mut self.footers);
+        self.contents = transpose_rows(rows);
+        self.sections.clear();
+        self.columns.clear();
+        self.depths.clear();
+    }
+
     /// Creates the table as a `String`
     pub fn tabulate(&self) -> String {
-        let style = &self.style;
-        let headers = &self.headers;
-        let contents = &self.contents;
+        self.render(&RenderOptions::default()).0.remove(0)
+    }
+
+    /// Creates the table as a `String`, its columns being at least as wide as the ones of the
+    /// layout (see [`Layout`](struct.Layout.html))
+    pub fn tabulate_with_layout(&self, layout: &Layout) -> String {
+        let options = RenderOptions {
+            layout: Some(layout),
+            ..Default::default()
+        };
+        self.render(&options).0.remove(0)
+    }
+
+    /// Creates the table as a `String`, fitted in `width` columns
+    ///
+    /// The widest text columns are shrunk (lowest priority first) using their
+    /// [`Overflow`](enum.Overflow.html) policy until the table fits or all of them reached their
+    /// minimum width. With [`Fit::HideColumns`](enum.Fit.html#variant.HideColumns), the columns
+    /// with the lowest priority are hidden first.
+    /// # Example
+    /// ```
+    /// use stybulate::{Table, Style, Cell, Headers};
+    /// let table = Table::new(
+    ///     Style::Grid,
+    ///     vec![vec![Cell::from("log"), Cell::from("connection refused by peer")]],
+    ///     Some(Headers::from(vec!["level", "message"])),
+    /// );
+    /// let expected = [
+    ///     "+---------+--------------+",
+    ///     "| level   | message      |",
+    ///     "+=========+==============+",
+    ///     "| log     | connection   |",
+    ///     "|         | refused by   |",
+    ///     "|         | peer         |",
+    ///     "+---------+--------------+",
+    /// ].join("\n");
+    /// assert_eq!(expected, table.tabulate_to_width(26));
+    /// ```
+    pub fn tabulate_to_width(&self, width: usize) -> String {
+        let options = RenderOptions {
+            width: Some(width),
+            ..Default::default()
+        };
+        self.render(&options).0.remove(0)
+    }
+
+    /// Creates the pages of the table, each of them with the headers and ending with a line
+    /// like "page 2/5"
+    ///
+    /// The pages are split between rows (but not under cells spanning them), as few as the given
+    /// size allows. The title is on the first page, the footers and the caption on the last one.
+    /// # Example
+    /// ```
+    /// use stybulate::{Table, Style, Cell, Headers, PageSize};
+    /// let table = Table::new(
+    ///     Style::Simple,
+    ///     vec![vec![Cell::Int(1)], vec![Cell::Int(2)], vec![Cell::Int(3)]],
+    ///     Some(Headers::from(vec!["n"])),
+    /// );
+    /// let pages = table.paginate(PageSize::Rows(2));
+    /// assert_eq!(["  n\n---\n  1\n  2\npage 1/2", "  n\n---\n  3\npage 2/2"], &pages[..]);
+    /// ```
+    pub fn paginate(&self, size: PageSize) -> Vec<String> {
+        let options = RenderOptions {
+            page_size: Some(size),
+            ..Default::default()
+        };
+        self.render(&options).0
+    }
+
+    /// Creates the pages of the table (a single one unless paginated), and its layout before
+    /// it is fitted in a width
+    fn render(&self, options: &RenderOptions) -> (Vec<String>, Layout) {
+        // the left margin is taken from the width
+        let options = match self.margin {
+            Some(Margin::Left(indent)) => RenderOptions {
+                width: options.width.map(|width| width.saturating_sub(indent)),
+                ..*options
+            },
+            _ => *options,
+        };
+        let (mut pages, layout) = self.render_lines(&options);
+        self.place_lines(&mut pages);
+        let pages = pages.into_iter().map(|lines| lines.join("\n")).collect();
+        (pages, layout)
+    }
+
+    /// Creates the lines of each page of the table, and the widths of its columns
+    fn render_lines(&self, options: &RenderOptions) -> (Vec<Vec<String>>, Layout) {
+        let RenderOptions {
+            width,
+            fill,
+            layout,
+            page_size,
+        } = *options;
+        if self.expanded {
+            return (vec![self.render_expanded(width)], Layout::default());
+        }
         let str_align = &self.str_align;
         let num_align = &self.num_align;
-        #[allow(unused_mut)]
-        let mut fmt = style.to_format();
-        #[cfg(feature = "ansi_term_style")]
-        {
-            if let Some(style) = self.border_style {
-                fmt.apply_style(style);
+        let fmt = self.format();
+        // position of each cell in the grid
+        let levels = self.headers.as_ref().map_or(&[][..], |h| &h.levels);
+        let (mut headers, mut headers_covered) = place_rows(levels);
+        // cells spanning rows stay in their section
+        let mut contents = Vec::with_capacity(self.contents.len());
+        let mut covered = Vec::with_capacity(self.contents.len());
+        let mut rows_left = Vec::with_capacity(self.contents.len());
+        let mut start = 0;
+        for (end, _) in self.sections.iter().chain(&[(self.contents.len(), None)]) {
+            let (placed, placed_covered) = place_rows(&self.contents[start..*end]);
+            contents.extend(placed);
+            covered.extend(placed_covered);
+            rows_left.extend((1..=end - start).rev());
+            start = *end;
+        }
+        let (mut footers, mut footers_covered) = place_rows(&self.footers);
+        // the index column comes first, shifting the others
+        let offset = usize::from(self.index.is_some());
+        // numbers too large for a Cell::Int are written as texts
+        let number = |i: usize| match i32::try_from(i) {
+            Ok(i) => Cell::Int(i),
+            Err(_) => Cell::from(&i.to_string()),
+        };
+        let index_cells: Vec<Cell> = match &self.index {
+            None => vec![],
+            Some(Index::FromZero) => (0..self.contents.len()).map(number).collect(),
+            Some(Index::FromOne) => (1..=self.contents.len()).map(number).collect(),
+            Some(Index::Labels(labels)) => (0..self.contents.len())
+                .map(|i| Cell::from(labels.get(i).map_or("", String::as_str)))
+                .collect(),
+        };
+        let index_header = Cell::from(self.index_header.as_str()).with_rowspan(levels.len());
+        if offset > 0 {
+            shift_cols(&mut headers, &mut headers_covered);
+            for (i, (row, covered)) in headers.iter_mut().zip(&mut headers_covered).enumerate() {
+                if i == 0 {
+                    row.insert(0, (0, &index_header));
+                } else {
+                    covered.insert(0, 0);
+                }
             }
+            shift_cols(&mut contents, &mut covered);
+            for (row, cell) in contents.iter_mut().zip(&index_cells) {
+                row.insert(0, (0, cell));
+            }
+            shift_cols(&mut footers, &mut footers_covered);
         }
+        // settings of the columns of the contents
+        let column = |col: usize| {
+            col.checked_sub(offset)
+                .and_then(|col| self.columns.get(&col))
+        };
         // number of columns
-        let header_len = if let Some(h) = headers { h.len() } else { 0 };
-        let col_nb = cmp::max(
-            header_len,
-            *contents.iter().map(Vec::len).max().get_or_insert(0),
-        );
+        let col_nb = headers
+            .iter()
+            .chain(contents.iter())
+            .chain(footers.iter())
+            .flatten()
+            .map(|(col, cell)| col + get_colspan(cell))
+            .max()
+            .unwrap_or(0);
+        let all_cols: Vec<usize> = (0..col_nb).collect();
         // column specs = [0]: true if only made of numbers & [1]: digits offset
-        let col_spec = get_col_specs(col_nb, contents);
-        // max width of the content of each column
-        let col_width = get_col_width(col_nb, headers, contents, &col_spec, num_align);
-        // Build the lines
-        let mut lines = vec![];
-        // lineabove
-        if !(headers.is_some() && fmt.hidelineaboveifheader) {
-            if let Some(lineabove) = fmt.lineabove {
-                lines.push(create_line(&lineabove, &col_width));
+        let mut col_spec = get_col_specs(col_nb, contents.iter().chain(footers.iter()));
+        // the stub column holds row headers, not data
+        let stub = Some(offset).filter(|col| self.stub && *col < col_nb);
+        if let Some(col) = stub {
+            col_spec[col].0 = false;
+        }
+        // summary rows, computed over the numbers of the contents
+        let numeric: Vec<bool> = col_spec
+            .iter()
+            .enumerate()
+            .map(|(col, spec)| spec.0 && col >= offset)
+            .collect();
+        let summary = summary::summarize(&self.summary, &numeric, offset, &contents);
+        // a mean keeps at least one decimal, even in a column of integers
+        if self.summary.contains(&Agg::Mean) {
+            for (spec, _) in col_spec.iter_mut().zip(&numeric).filter(|(_, n)| **n) {
+                spec.1 = cmp::max(spec.1, 1);
             }
         }
-        if let Some(headers) = headers {
-            // headerrow
-            let headers: Vec<&Box<dyn Unstyle>> = headers.to_ref_vec();
-            for data in create_data_lines(&headers, str_align, num_align, &col_width, &col_spec) {
-                lines.push(create_data_line(&fmt.headerrow, col_nb, &data));
+        let (summary, summary_covered) = place_rows(&summary);
+        // alignment of each column
+        let col_align: Vec<Align> = (0..col_nb)
+            .map(|col| {
+                let align = match col < offset {
+                    true => self.index_align,
+                    false => column(col).and_then(|c| c.align),
+                };
+                if let Some(align) = align {
+                    align
+                } else if col_spec[col].0 {
+                    *num_align
+                } else {
+                    *str_align
+                }
+            })
+            .collect();
+        // texts of the cells, numbers being formatted with their column precision
+        let headers: Vec<Vec<Slot>> = headers
+            .iter()
+            .zip(&headers_covered)
+            .enumerate()
+            .map(|(i, (row, covered))| {
+                let level_align = match self.headers.as_ref().and_then(|h| h.aligns[i]) {
+                    Some(align) => vec![align; col_nb],
+                    None => col_align.clone(),
+                };
+                let rows_left = levels.len() - i;
+                get_slots(
+                    row,
+                    covered,
+                    rows_left,
+                    col_nb,
+                    &col_spec,
+                    &level_align,
+                    true,
+                )
+            })
+            .collect();
+        let body_slots = |rows, covered, rows_left| {
+            get_body_slots(rows, covered, rows_left, col_nb, &col_spec, &col_align)
+        };
+        let mut contents = body_slots(&contents, &covered, &rows_left);
+        // guides of the tree before the first column of the contents
+        if !self.depths.is_empty() {
+            let guides = get_guides(&self.depths, self.contents.len());
+            for (row, guides) in contents.iter_mut().zip(guides) {
+                if let Some(slot) = row.iter_mut().find(|slot| slot.col == offset) {
+                    slot.guides = guides;
+                }
             }
-            // linebelowheader
-            if let Some(linebelowheader) = fmt.linebelowheader {
-                lines.push(create_line(&linebelowheader, &col_width));
+        }
+        #[cfg(feature = "ansi_term_style")]
+        {
+            if let (Some(col), Some(style)) = (stub, self.stub_style) {
+                let cells = contents.iter_mut().flatten().filter(|slot| slot.col == col);
+                for slot in cells {
+                    slot.styled = paint_lines(style, &slot.styled);
+                }
             }
         }
-        // loop on contents
-        for (i, content) in contents.iter().enumerate() {
-            // linebetweenrows
-            if i != 0 {
-                if let Some(linebetweenrows) = fmt.linebetweenrows.clone() {
-                    lines.push(create_line(&linebetweenrows, &col_width));
+        let footers_left: Vec<usize> = (1..=footers.len()).rev().collect();
+        let mut footers = body_slots(&footers, &footers_covered, &footers_left);
+        let summary_left: Vec<usize> = (1..=summary.len()).rev().collect();
+        footers.extend(body_slots(&summary, &summary_covered, &summary_left));
+        // section headings among the contents, with the line above each row
+        let mut rows = contents.into_iter();
+        let mut sections = self.sections.iter().peekable();
+        let mut body = Vec::with_capacity(self.contents.len() + self.sections.len());
+        for i in 0..=self.contents.len() {
+            let mut line = fmt.linebetweenrows.as_ref();
+            while let Some((_, heading)) = sections.peek().copied().filter(|(start, _)| *start == i)
+            {
+                sections.next();
+                line = fmt.linebetweensections.as_ref();
+                if let Some(heading) = heading {
+                    body.push((vec![self.heading_slot(heading, col_nb)], line));
+                    line = fmt.linebetweenrows.as_ref();
                 }
             }
-            // datarow
-            let mut unstylable_content = Vec::with_capacity(content.len());
-            let mut temp_unstyle_store = HashMap::new();
-            let mut temp_strings_store = HashMap::new();
-            for (col, cell) in content.iter().enumerate() {
-                if let Some(u) = cell.to_unstylable() {
-                    temp_unstyle_store.insert(col, u);
-                } else {
-                    temp_strings_store.insert(
-                        col,
-                        Box::new(cell.to_string_with_precision(col_spec[col].1).unwrap())
-                            as Box<dyn Unstyle>,
-                    );
+            if let Some(row) = rows.next() {
+                body.push((row, line));
+            }
+        }
+        let (contents, lines_above): (Vec<_>, Vec<_>) = body.into_iter().unzip();
+        let all_slots: Vec<&Slot> = headers
+            .iter()
+            .chain(contents.iter())
+            .chain(footers.iter())
+            .flatten()
+            .collect();
+        // max width of the content of each column
+        let col_max_width: Vec<Option<usize>> = (0..col_nb)
+            .map(|col| match column(col).and_then(|c| c.width) {
+                Some(Width::Exactly(width)) => Some(width),
+                _ => column(col).and_then(|c| c.max_width).or(self.max_width),
+            })
+            .collect();
+        let mut col_width = get_col_width(col_nb, &all_slots, &col_max_width);
+        // widths set on the columns, the fixed ones being widened last
+        let col_set_width: Vec<usize> = (0..col_nb)
+            .map(|col| match column(col).and_then(|c| c.width) {
+                Some(Width::AtLeast(width)) | Some(Width::Exactly(width)) => width,
+                None => 0,
+            })
+            .collect();
+        for (w, set) in col_width.iter_mut().zip(&col_set_width) {
+            *w = cmp::max(*w, *set);
+        }
+        let fixed: Vec<bool> = (0..col_nb)
+            .map(|col| matches!(column(col).and_then(|c| c.width), Some(Width::Exactly(_))))
+            .collect();
+        // rules after some columns, their separators being wider
+        let col_rule: Vec<bool> = (0..col_nb)
+            .map(|col| (col < offset && self.index_rule) || Some(col) == stub)
+            .collect();
+        let col_seps = get_sep_widths(&fmt.datarow, &get_rules(&col_rule, &all_cols));
+        spread_spans(
+            &mut col_width,
+            &all_slots,
+            &all_cols,
+            &col_seps,
+            &col_max_width,
+            &fixed,
+        );
+        // the line above the table, where the title is embedded if it is drawn
+        let lineabove = fmt
+            .lineabove
+            .as_ref()
+            .filter(|_| !(self.headers.is_some() && fmt.hidelineaboveifheader));
+        if let Some(title) = &self.title {
+            // the title is as wide as all the columns, with a space on each side in a line
+            let row = &fmt.datarow;
+            let borders = wrap::width(&row.begin) + wrap::width(&row.end);
+            let width = match lineabove {
+                Some(line) => wrap::width(&line.begin) + wrap::width(&line.end) + 2,
+                None => 0,
+            };
+            let width = (wrap::width(title) + width).saturating_sub(borders);
+            widen(&mut col_width, width, &col_seps, &fixed);
+        }
+        if let Some(fill) = fill {
+            let row = &fmt.datarow;
+            let borders = wrap::width(&row.begin) + wrap::width(&row.end);
+            widen(
+                &mut col_width,
+                fill.saturating_sub(borders),
+                &col_seps,
+                &fixed,
+            );
+        }
+        if let Some(layout) = layout {
+            layout.widen(&mut col_width);
+        }
+        let table_layout = Layout::from(col_width.clone());
+        // blocks of columns to render, hidden, split or shrunk to fit in the given width
+        let mut blocks = vec![all_cols.clone()];
+        if let Some(width) = width {
+            let table_width = |visible: &[usize]| {
+                let rules = get_rules(&col_rule, visible);
+                get_table_width(&fmt, &select_cols(&col_width, visible), &rules)
+            };
+            let priority: Vec<i32> = (0..col_nb)
+                .map(|col| column(col).map_or(0, |c| c.priority))
+                .collect();
+            // the index, stub and key columns are never hidden and repeated in each block
+            let keys = offset + cmp::max(self.key_cols, usize::from(self.stub));
+            match self.fit {
+                Fit::Shrink => (),
+                Fit::HideColumns => {
+                    // lowest priority first, then rightmost first
+                    let visible = &mut blocks[0];
+                    while table_width(visible) > width {
+                        let hidden = visible
+                            .iter()
+                            .rev()
+                            .filter(|col| **col >= keys)
+                            .min_by_key(|col| priority[**col]);
+                        match hidden.copied() {
+                            Some(hidden) if visible.len() > 1 => {
+                                visible.retain(|col| *col != hidden)
+                            }
+                            _ => break,
+                        }
+                    }
                 }
+                Fit::Split => blocks = split_cols(&all_cols, keys, width, table_width),
             }
-            for col in 0..col_nb {
-                if let Some(u) = temp_unstyle_store.get(&col) {
-                    unstylable_content.push(*u);
-                } else {
-                    unstylable_content.push(temp_strings_store.get(&col).unwrap());
+            // the spanning cells over hidden columns are spread over the visible ones
+            for visible in blocks.iter().filter(|visible| visible.len() < col_nb) {
+                let mut widths = select_cols(&col_width, visible);
+                spread_spans(
+                    &mut widths,
+                    &all_slots,
+                    visible,
+                    &get_sep_widths(&fmt.datarow, &get_rules(&col_rule, visible)),
+                    &select_cols(&col_max_width, visible),
+                    &select_cols(&fixed, visible),
+                );
+                for (col, w) in visible.iter().zip(widths) {
+                    col_width[*col] = w;
                 }
             }
-            for data in create_data_lines(
-                &unstylable_content,
-                str_align,
-                num_align,
-                &col_width,
-                &col_spec,
-            ) {
-                lines.push(create_data_line(&fmt.datarow, col_nb, &data));
+            // width of the columns if all their texts were cut to a single char
+            let floor = get_col_width(col_nb, &all_slots, &vec![Some(1); col_nb]);
+            let min_width: Vec<usize> = (0..col_nb)
+                .map(|col| {
+                    let min = column(col).and_then(|c| c.min_width).unwrap_or(0);
+                    let min = cmp::max(min, col_set_width[col]);
+                    cmp::min(col_width[col], cmp::max(floor[col], min))
+                })
+                .collect();
+            for visible in &blocks {
+                let rules = get_rules(&col_rule, visible);
+                let visible_width =
+                    get_table_width(&fmt, &select_cols(&col_width, visible), &rules);
+                if visible_width > width {
+                    let mut widths = select_cols(&col_width, visible);
+                    shrink_col_width(
+                        &mut widths,
+                        &select_cols(&min_width, visible),
+                        &select_cols(&priority, visible),
+                        visible_width - width,
+                    );
+                    for (col, w) in visible.iter().zip(widths) {
+                        col_width[*col] = w;
+                    }
+                }
             }
         }
-        // linebelow
-        if !(headers.is_some() && fmt.hidelinebelowifheader) {
-            if let Some(linebelow) = fmt.linebelow {
-                lines.push(create_line(&linebelow, &col_width));
+        // texts wider than their column are wrapped or truncated
+        let col_overflow: Vec<Overflow> = (0..col_nb)
+            .map(|col| {
+                column(col)
+                    .and_then(|c| c.overflow)
+                    .unwrap_or(self.overflow)
+            })
+            .collect();
+        let fit = |slot: &Slot, width: usize| {
+            fit_text(slot, width, col_overflow[slot.col], &self.truncation_marker)
+        };
+        // Build the pages of lines, the blocks being separated by an empty line unless paginated
+        let mut pages: Vec<Vec<String>> = vec![vec![]];
+        let mut table_width = 0;
+        let linebetweenheaders = fmt
+            .linebetweenheaders
+            .as_ref()
+            .filter(|_| self.header_lines);
+        for (i, visible) in blocks.iter().enumerate() {
+            let widths = select_cols(&col_width, visible);
+            let rules = get_rules(&col_rule, visible);
+            let seps = get_sep_widths(&fmt.datarow, &rules);
+            // cells of the visible columns, with their fitted texts
+            let headers: Vec<_> = headers
+                .iter()
+                .map(|row| place_slots(row, visible, &widths, &seps, fit))
+                .collect();
+            let contents: Vec<_> = contents
+                .iter()
+                .map(|row| place_slots(row, visible, &widths, &seps, fit))
+                .collect();
+            let footers: Vec<_> = footers
+                .iter()
+                .map(|row| place_slots(row, visible, &widths, &seps, fit))
+                .collect();
+            let header_heights = get_heights(&headers);
+            let heights = get_heights(&contents);
+            let footer_heights = get_heights(&footers);
+            table_width = get_table_width(&fmt, &widths, &rules);
+            let block = Block {
+                fmt: &fmt,
+                lineabove,
+                linebetweenheaders,
+                widths,
+                rules,
+                headers: stack_rows(&headers, &header_heights),
+                header_heights,
+                contents: stack_rows(&contents, &heights),
+                heights,
+                lines_above: &lines_above,
+                footers: stack_rows(&footers, &footer_heights),
+                footer_heights,
+            };
+            let groups = self.group_block_rows(&block, page_size);
+            let pages_groups: Vec<&[Range<usize>]> = match page_size {
+                Some(_) => groups.chunks(1).collect(),
+                None => vec![&groups],
+            };
+            for (p, groups) in pages_groups.iter().enumerate() {
+                if page_size.is_none() && i > 0 {
+                    pages[0].push(String::new());
+                } else if page_size.is_some() && i + p > 0 {
+                    pages.push(vec![]);
+                }
+                // the title on the first page, the footers on the last one
+                let title = self.title.as_ref().filter(|_| i == 0 && p == 0);
+                let last = p + 1 == pages_groups.len();
+                let lines = self.draw_page(&block, groups, title, last);
+                pages.last_mut().unwrap().extend(lines);
             }
         }
-        // finally join all lines
-        lines.join("\n")
+        let hidden = all_cols
+            .iter()
+            .filter(|col| !blocks.iter().any(|visible| visible.contains(col)))
+            .count();
+        self.add_notes(&mut pages, table_width, hidden, page_size.is_some());
+        (pages, table_layout)
     }
-}
 
-// --------------------------- Private ---------------------------
-
-fn get_col_width<'a>(
-    col_nb: usize,
-    headers: &Option<Headers>,
-    contents: &[Vec<Cell<'a>>],
-    col_spec: &[(bool, usize)],
-    num_align: &Align,
-) -> Vec<usize> {
-    let mut col_width = vec![0; col_nb];
-    for col in 0..col_nb {
-        let mut max = 0;
-        if let Some(headers) = headers {
-            if let Some(h) = headers.get(col) {
-                max = *h
-                    .unstyle()
-                    .split('\n')
-                    .map(|s| UnicodeWidthStr::width(s as &str))
-                    .max()
-                    .get_or_insert(0)
-                    + MIN_PADDING;
+    /// Adds the caption and the note of the `hidden` columns after the table, and the page
+    /// numbers if `paginated`
+    fn add_notes(
+        &self,
+        pages: &mut [Vec<String>],
+        table_width: usize,
+        hidden: usize,
+        paginated: bool,
+    ) {
+        // caption
+        let lines = pages.last_mut().unwrap();
+        if let Some((caption, align)) = &self.caption {
+            let caption = wrap::wrap(caption, table_width);
+            let unstyled = AsciiEscapedString::from(&caption).unstyle();
+            for i in 0..=unstyled.matches('\n').count() {
+                let line = format_text(&caption, &unstyled, i, align, table_width, 0);
+                lines.push(line.trim_end().to_string());
             }
         }
-        for row in contents.iter() {
-            if let Some(c) = row.get(col) {
-                let width = if col_spec[col].0 /* a number */ && num_align == &Align::Decimal && col_spec[col].1 > 0
-                {
-                    c.to_string_with_precision(col_spec[col].1).unwrap().len()
-                } else if let Some(u) = c.to_unstylable() {
-                    *u.unstyle()
-                        .split('\n')
-                        .map(|s| UnicodeWidthStr::width(s as &str))
-                        .max()
-                        .get_or_insert(0)
-                } else {
-                    c.to_string().unwrap().len()
-                };
-                max = cmp::max(width, max);
+        // hidden columns note
+        if self.hidden_note && hidden > 0 {
+            lines.push(match hidden {
+                1 => String::from("1 column hidden"),
+                n => format!("{} columns hidden", n),
+            });
+        }
+        // page numbers
+        if paginated {
+            let nb = pages.len();
+            for (p, lines) in pages.iter_mut().enumerate() {
+                lines.push(format!("page {}/{}", p + 1, nb));
             }
         }
-        col_width[col] = max;
     }
-    col_width
-}
 
-fn get_col_specs(col_nb: usize, contents: &[Vec<Cell>]) -> Vec<(bool, usize)> {
-    let mut col_spec = vec![(false, 0); col_nb];
-    for (col, spec) in col_spec.iter_mut().enumerate().take(col_nb) {
-        let mut max = 0;
-        let mut all = true;
-        for row in contents.iter() {
-            if let Some(cell) = row.get(col) {
-                if cell.is_a_number() {
-                    max = cmp::max(max, cell.digits_len());
-                } else {
-                    all = false;
+    /// Groups of rows of the block, after which the headers are repeated or the pages split
+    fn group_block_rows(&self, block: &Block, page_size: Option<PageSize>) -> Vec<Range<usize>> {
+        let Block {
+            fmt,
+            lineabove,
+            linebetweenheaders,
+            headers,
+            header_heights,
+            contents,
+            heights,
+            lines_above,
+            footers,
+            footer_heights,
+            ..
+        } = block;
+        let has_headers = !headers.is_empty();
+        // rows spanning from the row above cannot start a group
+        let breakable: Vec<bool> = contents
+            .iter()
+            .map(|row| row.iter().all(|(_, line)| *line == 0))
+            .collect();
+        let size = page_size.or(self.header_repeat.filter(|_| has_headers));
+        let mut groups = match size {
+            Some(size) => {
+                let lines: Vec<usize> = lines_above
+                    .iter()
+                    .map(|line| usize::from(line.is_some()))
+                    .collect();
+                // the line above the headers (or the first row) and the headers
+                let mut overhead = usize::from(lineabove.is_some())
+                    + header_heights.iter().sum::<usize>()
+                    + linebetweenheaders.map_or(0, |_| headers.len().saturating_sub(1))
+                    + usize::from(has_headers && fmt.linebelowheader.is_some());
+                let mut tail = 0;
+                if page_size.is_some() {
+                    // linebelow and page number
+                    let hidden = has_headers && fmt.hidelinebelowifheader;
+                    overhead += usize::from(fmt.linebelow.is_some() && !hidden) + 1;
+                    // footers on the last page
+                    if !footers.is_empty() {
+                        tail = footer_heights.iter().sum::<usize>()
+                            + usize::from(fmt.lineabovefooter.is_some())
+                            + fmt
+                                .linebetweenrows
+                                .as_ref()
+                                .map_or(0, |_| footers.len() - 1);
+                    }
                 }
+                group_rows(size, heights, &lines, &breakable, overhead, tail)
             }
+            None => vec![],
+        };
+        if groups.is_empty() {
+            groups.push(0..contents.len());
         }
-        *spec = (all, max);
+        groups
     }
-    col_spec
-}
-
-fn create_line(line: &style::Line, col_width: &[usize]) -> String {
-    (line.begin.clone()
-        + &col_width
-            .iter()
-            .map(|w| line.hline.repeat(*w))
-            .collect::<Vec<String>>()
-            .join(&line.sep)
-        + &line.end)
-        .trim_end()
-        .to_string()
-}
 
-fn create_data_line(row: &style::DataRow, col_nb: usize, content: &[String]) -> String {
-    let mut v = Vec::with_capacity(col_nb);
-    for col in 0..col_nb {
-        if let Some(c) = content.get(col) {
-            v.push(String::from(c));
-        } else {
-            v.push(String::from(""));
+    /// Lines of a page of the block, made of `groups` of rows (the headers being repeated
+    /// before each of them), with the `title` and the footers if `last`
+    fn draw_page(
+        &self,
+        block: &Block,
+        groups: &[Range<usize>],
+        title: Option<&String>,
+        last: bool,
+    ) -> Vec<String> {
+        let Block {
+            fmt,
+            lineabove,
+            linebetweenheaders,
+            widths,
+            rules,
+            headers,
+            header_heights,
+            contents,
+            heights,
+            lines_above,
+            footers,
+            footer_heights,
+        } = block;
+        let nb = widths.len();
+        let full = vec![true; nb];
+        let has_headers = !headers.is_empty();
+        let table_width = get_table_width(fmt, widths, rules);
+        let mut lines = vec![];
+        // rows of the page, the footers being on its last one
+        let page_rows = &contents[groups[0].start..groups[groups.len() - 1].end];
+        let page_footers = match last {
+            true => &footers[..],
+            false => &footers[..0],
+        };
+        // lineabove, with the title
+        if let Some(lineabove) = lineabove {
+            let below = headers
+                .first()
+                .or(page_rows.first())
+                .or(page_footers.first());
+            let below = get_splits(below.map_or(&[], Vec::as_slice), nb);
+            let line = create_line(
+                lineabove,
+                &fmt.datarow,
+                widths,
+                rules,
+                &below,
+                &below,
+                &full,
+            );
+            lines.push(match title {
+                Some(title) => self.embed_title(&line, lineabove, title),
+                None => line,
+            });
+        } else if let Some(title) = title {
+            let title = wrap::truncate(title, table_width, &self.truncation_marker);
+            let unstyled = AsciiEscapedString::from(&title).unstyle();
+            let line = format_text(&title, &unstyled, 0, &Align::Center, table_width, 0);
+            lines.push(line.trim_end().to_string());
         }
-    }
-    (row.begin.clone() + &v.join(&row.sep) + &row.end)
-        .trim_end()
-        .to_string()
-}
-
-#[allow(clippy::borrowed_box)]
-fn format_unstylable<'a>(
-    word: &Box<dyn Unstyle + 'a>,
-    line_idx: usize,
-    align: &Align,
-    width: usize,
-) -> String {
-    if let Some(unstyled_word) = word.unstyle().split('\n').nth(line_idx) {
-        let word = word.to_string();
-        let word = word
-            .split('\n')
-            .nth(line_idx)
-            .expect("unstyled word can't have more \\n than styled one");
-        let width =
-            width - (UnicodeWidthStr::width(unstyled_word as &str) - unstyled_word.chars().count());
-        let formatted = match align {
-            Align::Right => format!("{:>width$}", unstyled_word, width = width),
-            Align::Left => format!("{:<width$}", unstyled_word, width = width),
-            Align::Center => format!("{:^width$}", unstyled_word, width = width),
-            Align::Decimal => {
-                let mut out = format!("{:>width$}", unstyled_word, width = width);
-                if let Some(dot) = out.rfind('.') {
-                    if out[(dot + 1)..].bytes().all(|c| c == b'0') {
-                        out.replace_range(dot.., &" ".repeat(out.len() - dot));
-                    } else {
-                        for i in (dot + 1..out.len()).rev() {
-                            if out.as_bytes()[i] == b'0' {
-                                out.replace_range(i..i + 1, " ");
-                            }
-                        }
-                    }
+        for (g, range) in groups.iter().enumerate() {
+            let rows = &contents[range.clone()];
+            // the line above the repeated headers
+            if g > 0 {
+                if let Some(line) = lines_above[range.start] {
+                    let above = get_splits(&contents[range.start - 1], nb);
+                    let below = get_splits(&headers[0], nb);
+                    lines.push(create_line(
+                        line,
+                        &fmt.datarow,
+                        widths,
+                        rules,
+                        &above,
+                        &below,
+                        &full,
+                    ));
                 }
-                out
             }
-        };
-        if unstyled_word != word {
-            formatted.replace(&unstyled_word, word)
-        } else {
-            formatted
+            // headerrows & linebetweenheaders
+            lines.extend(create_rows(
+                &fmt.headerrow,
+                &vec![*linebetweenheaders; headers.len()],
+                headers,
+                header_heights,
+                widths,
+                rules,
+            ));
+            // linebelowheader
+            if let (Some(linebelowheader), Some(last)) = (&fmt.linebelowheader, headers.last()) {
+                let above = get_splits(last, nb);
+                let below = rows.first().or(page_footers.first());
+                let below = get_splits(below.map_or(&[], Vec::as_slice), nb);
+                lines.push(create_line(
+                    linebelowheader,
+                    &fmt.datarow,
+                    widths,
+                    rules,
+                    &above,
+                    &below,
+                    &full,
+                ));
+            }
+            // datarows & linebetweenrows
+            lines.extend(create_rows(
+                &fmt.datarow,
+                &lines_above[range.clone()],
+                rows,
+                &heights[range.clone()],
+                widths,
+                rules,
+            ));
         }
-    } else {
-        " ".repeat(width)
+        // lineabovefooter (the line below the headers is enough without contents)
+        if let (Some(lineabovefooter), Some(last), Some(first)) =
+            (&fmt.lineabovefooter, page_rows.last(), page_footers.first())
+        {
+            let above = get_splits(last, nb);
+            let below = get_splits(first, nb);
+            lines.push(create_line(
+                lineabovefooter,
+                &fmt.datarow,
+                widths,
+                rules,
+                &above,
+                &below,
+                &full,
+            ));
+        }
+        // footerrows & linebetweenrows
+        lines.extend(create_rows(
+            &fmt.datarow,
+            &vec![fmt.linebetweenrows.as_ref(); page_footers.len()],
+            page_footers,
+            &footer_heights[..page_footers.len()],
+            widths,
+            rules,
+        ));
+        // linebelow
+        if !(has_headers && fmt.hidelinebelowifheader) {
+            if let Some(linebelow) = &fmt.linebelow {
+                let above = page_footers.last().or(page_rows.last()).or(headers.last());
+                let above = get_splits(above.map_or(&[], Vec::as_slice), nb);
+                lines.push(create_line(
+                    linebelow,
+                    &fmt.datarow,
+                    widths,
+                    rules,
+                    &above,
+                    &above,
+                    &full,
+                ));
+            }
+        }
+        lines
     }
-}
 
-#[allow(clippy::borrowed_box)]
-fn create_data_lines<'a>(
-    content: &[&Box<dyn Unstyle + 'a>],
-    str_align: &Align,
-    num_align: &Align,
-    col_width: &[usize],
-    col_spec: &[(bool, usize)],
-) -> Vec<Vec<String>> {
-    let lines_nb = content.iter().map(|u| u.nb_of_lines()).max().unwrap();
-    let mut lines = Vec::with_capacity(lines_nb);
-    for i in 0..lines_nb {
-        let formatted: Vec<_> = content
+    /// Pads the lines to the width of the widest one if asked, and indents them
+    fn place_lines(&self, pages: &mut [Vec<String>]) {
+        let lines = pages.iter().flatten();
+        let width = lines.map(|line| wrap::width(line)).max().unwrap_or(0);
+        let indent = match self.margin {
+            None => 0,
+            Some(Margin::Left(indent)) => indent,
+            Some(Margin::Center(outer)) => outer.saturating_sub(width) / 2,
+        };
+        let indent = " ".repeat(indent);
+        for line in pages.iter_mut().flatten() {
+            if self.pad_lines {
+                let pad = width - wrap::width(line);
+                line.push_str(&" ".repeat(pad));
+            }
+            if !line.is_empty() {
+                line.insert_str(0, &indent);
+            }
+        }
+    }
+
+    /// Format of the style, with the table borders settings
+    fn format(&self) -> style::TableFormat {
+        let mut fmt = self.style.to_format();
+        if self.borderless {
+            fmt.remove_borders();
+        }
+        #[cfg(feature = "ansi_term_style")]
+        {
+            if let Some(style) = self.border_style {
+                fmt.apply_style(style);
+            }
+        }
+        fmt
+    }
+
+    /// Creates the lines of the records of the expanded mode, fitted in `width` columns if any
+    fn render_expanded(&self, width: Option<usize>) -> Vec<String> {
+        let fmt = self.format();
+        let (contents, covered) = place_rows(&self.contents);
+        let levels = self.headers.as_ref().map_or(&[][..], |h| &h.levels);
+        let (headers, headers_covered) = place_rows(levels);
+        let col_nb = headers
             .iter()
-            .enumerate()
-            .map(|(col, text)| {
-                let align = if col_spec[col].0 {
-                    // numbers only
-                    &num_align
-                } else {
-                    // strings only
-                    &str_align
-                };
-                format_unstylable(text, i, align, col_width[col])
+            .chain(contents.iter())
+            .flatten()
+            .map(|(col, cell)| col + get_colspan(cell))
+            .max()
+            .unwrap_or(0);
+        let col_align = vec![Align::Left; col_nb];
+        // label of each column: its headers of all levels
+        let headers: Vec<Slot> = headers
+            .iter()
+            .zip(&headers_covered)
+            .flat_map(|(row, covered)| {
+                let col_spec = get_col_specs(col_nb, iter::once(row));
+                get_slots(row, covered, 1, col_nb, &col_spec, &col_align, false)
+            })
+            .filter(|slot| !slot.unstyled.is_empty())
+            .collect();
+        let labels: Vec<Slot> = (0..col_nb)
+            .map(|col| {
+                let slots = headers
+                    .iter()
+                    .filter(|slot| (slot.col..slot.col + slot.span).contains(&col));
+                let (styled, unstyled): (Vec<_>, Vec<_>) = slots
+                    .map(|slot| (slot.styled.as_str(), slot.unstyled.as_str()))
+                    .unzip();
+                Slot {
+                    styled: styled.join(" "),
+                    unstyled: unstyled.join(" "),
+                    ..Slot::empty(0)
+                }
             })
             .collect();
-        lines.push(formatted);
+        // records of two columns: labels and values (in the second column)
+        let records: Vec<Vec<(usize, Slot)>> = contents
+            .iter()
+            .zip(&covered)
+            .map(|(row, covered)| {
+                // the numbers are not aligned across the records
+                let col_spec = get_col_specs(col_nb, iter::once(row));
+                let values = get_slots(row, covered, 1, col_nb, &col_spec, &col_align, false);
+                let values = values.into_iter().map(|value| {
+                    let col = value.col;
+                    (
+                        col,
+                        Slot {
+                            col: 1,
+                            span: 1,
+                            ..value
+                        },
+                    )
+                });
+                values.collect()
+            })
+            .collect();
+        let values = records.iter().flatten().map(|(_, value)| value);
+        let all_slots: Vec<&Slot> = labels.iter().chain(values).collect();
+        // without any label, the records only have the values
+        let visible: Vec<usize> = match labels.iter().all(|label| label.unstyled.is_empty()) {
+            true => vec![1],
+            false => vec![0, 1],
+        };
+        let col_width = get_col_width(2, &all_slots, &[None, self.max_width]);
+        let mut widths = select_cols(&col_width, &visible);
+        let rules = vec![false; visible.len() - 1];
+        // room for the widest record number, followed by a part of its line
+        let recordline = fmt.recordline();
+        let begin = |line: &style::Line| cmp::max(wrap::width(&line.begin), 1);
+        let record_end = |line: &style::Line, n: usize| {
+            begin(line) + format!("[ RECORD {} ]", n).len() + 1 + wrap::width(&line.end)
+        };
+        let record_end = cmp::max(
+            fmt.lineabove.as_ref().map_or(0, |line| record_end(line, 1)),
+            record_end(&recordline, records.len()),
+        );
+        let table_width = get_table_width(&fmt, &widths, &rules);
+        if table_width < record_end {
+            *widths.last_mut().unwrap() += record_end - table_width;
+        }
+        // the values are shrunk to fit in the width, down to the widest ones never cut
+        if let Some(width) = width {
+            let table_width = get_table_width(&fmt, &widths, &rules);
+            if table_width > width {
+                let floor = get_col_width(2, &all_slots, &[None, Some(1)])[1];
+                let values_width = widths.last_mut().unwrap();
+                *values_width = cmp::max(floor, values_width.saturating_sub(table_width - width));
+            }
+        }
+        let seps = get_sep_widths(&fmt.datarow, &rules);
+        let fit = |slot: &Slot, width: usize| {
+            fit_text(slot, width, self.overflow, &self.truncation_marker)
+        };
+        let full = vec![true; visible.len()];
+        let mut lines = vec![];
+        for (i, values) in records.iter().enumerate() {
+            // the line above the record, with its number
+            let line = match (i, &fmt.lineabove) {
+                (0, Some(lineabove)) => lineabove,
+                _ => &recordline,
+            };
+            let rule = create_line(line, &fmt.datarow, &widths, &rules, &full, &full, &full);
+            let start = begin(line);
+            let record = format!("[ RECORD {} ]", i + 1);
+            lines.push(match start + record.len() <= wrap::width(&rule) {
+                true => wrap::splice(&rule, start, &record),
+                false => rule,
+            });
+            // a row per column
+            let rows: Vec<Vec<Placed>> = values
+                .iter()
+                .map(|(col, value)| {
+                    let mut row = place_slots(
+                        slice::from_ref(&labels[*col]),
+                        &visible,
+                        &widths,
+                        &seps,
+                        fit,
+                    );
+                    row.extend(place_slots(
+                        slice::from_ref(value),
+                        &visible,
+                        &widths,
+                        &seps,
+                        fit,
+                    ));
+                    row
+                })
+                .collect();
+            let heights = get_heights(&rows);
+            let rows = stack_rows(&rows, &heights);
+            lines.extend(create_rows(
+                &fmt.datarow,
+                &vec![None; rows.len()],
+                &rows,
+                &heights,
+                &widths,
+                &rules,
+            ));
+        }
+        let hidden = self.headers.is_some() && fmt.hidelinebelowifheader;
+        if let (Some(linebelow), false) = (&fmt.linebelow, records.is_empty() || hidden) {
+            lines.push(create_line(
+                linebelow,
+                &fmt.datarow,
+                &widths,
+                &rules,
+                &full,
+                &full,
+                &full,
+            ));
+        }
+        lines
+    }
+
+    /// Slot of a section heading, spanning all the columns
+    fn heading_slot(&self, heading: &str, col_nb: usize) -> Slot<'static> {
+        let unstyled = AsciiEscapedString::from(heading).unstyle();
+        #[allow(unused_mut)]
+        let mut styled = String::from(heading);
+        #[cfg(feature = "ansi_term_style")]
+        {
+            if let Some(style) = self.section_style {
+                styled = paint_lines(style, heading);
+            }
+        }
+        Slot {
+            col: 0,
+            span: col_nb,
+            rowspan: 1,
+            styled,
+            unstyled,
+            number: false,
+            header: false,
+            align: Align::Left,
+            padding: 0,
+            table: None,
+            guides: None,
+        }
+    }
+
+    /// Embeds the title in a line, after its beginning
+    fn embed_title(&self, line: &str, fmt: &style::Line, title: &str) -> String {
+        let start = wrap::width(&fmt.begin);
+        let end = wrap::width(&fmt.end);
+        let available = wrap::width(line).saturating_sub(start + end + 2);
+        if available == 0 {
+            return line.to_string();
+        }
+        let title = wrap::truncate(title, available, &self.truncation_marker);
+        wrap::splice(line, start, &format!(" {} ", title))
+    }
+}
+
+// --------------------------- Private ---------------------------
+
+/// How a table is rendered
+#[derive(Clone, Copy, Default)]
+struct RenderOptions<'l> {
+    /// width to fit the table in
+    width: Option<usize>,
+    /// width to widen the table to
+    fill: Option<usize>,
+    /// column widths shared with other tables
+    layout: Option<&'l Layout>,
+    /// size of the pages, if paginated
+    page_size: Option<PageSize>,
+}
+
+/// Per column settings
+#[derive(Default)]
+struct ColumnFormat {
+    align: Option<Align>,
+    max_width: Option<usize>,
+    overflow: Option<Overflow>,
+    min_width: Option<usize>,
+    priority: i32,
+    width: Option<Width>,
+}
+
+/// A cell placed in the grid, with its text ready to be laid out
+struct Slot<'c> {
+    /// first column
+    col: usize,
+    /// number of columns
+    span: usize,
+    /// number of rows
+    rowspan: usize,
+    styled: String,
+    unstyled: String,
+    number: bool,
+    header: bool,
+    align: Align,
+    padding: usize,
+    /// nested table, rendered again to fill the width of the cell
+    table: Option<&'c Table<'c>>,
+    /// tree guides of the first line and of the other ones, before the text
+    guides: Option<(String, String)>,
+}
+
+impl Slot<'_> {
+    fn empty(col: usize) -> Self {
+        Self {
+            col,
+            span: 1,
+            rowspan: 1,
+            styled: String::new(),
+            unstyled: String::new(),
+            number: false,
+            header: false,
+            align: Align::Left,
+            padding: 0,
+            table: None,
+            guides: None,
+        }
+    }
+
+    /// Numbers and nested tables are never cut
+    fn is_cuttable(&self) -> bool {
+        !self.number && self.table.is_none()
+    }
+
+    /// Width of the tree guides before the content
+    fn guides_width(&self) -> usize {
+        self.guides
+            .as_ref()
+            .map_or(0, |(first, _)| get_text_width(first))
+    }
+
+    /// Width of the content, padding included
+    fn width(&self) -> usize {
+        let min_padding = if self.header { MIN_PADDING } else { 0 };
+        get_text_width(&self.unstyled) + self.guides_width() + 2 * self.padding + min_padding
+    }
+}
+
+/// A block of visible columns, with its stacked rows and the lines around them
+struct Block<'b> {
+    fmt: &'b style::TableFormat,
+    lineabove: Option<&'b style::Line>,
+    linebetweenheaders: Option<&'b style::Line>,
+    widths: Vec<usize>,
+    rules: Vec<bool>,
+    headers: Vec<Vec<(&'b Placed<'b>, usize)>>,
+    header_heights: Vec<usize>,
+    contents: Vec<Vec<(&'b Placed<'b>, usize)>>,
+    heights: Vec<usize>,
+    /// line above each row of the contents
+    lines_above: &'b [Option<&'b style::Line>],
+    footers: Vec<Vec<(&'b Placed<'b>, usize)>>,
+    footer_heights: Vec<usize>,
+}
+
+/// A slot spanning `span` visible columns from the `first` one, its text fitted in its width
+struct Placed<'s> {
+    first: usize,
+    span: usize,
+    width: usize,
+    slot: &'s Slot<'s>,
+    text: Option<(String, String)>,
+}
+
+impl<'s> Placed<'s> {
+    /// Styled and unstyled texts
+    fn texts(&self) -> (&str, &str) {
+        match &self.text {
+            Some((styled, unstyled)) => (styled, unstyled),
+            None => (&self.slot.styled, &self.slot.unstyled),
+        }
+    }
+
+    /// Number of lines
+    fn height(&self) -> usize {
+        self.texts().1.matches('\n').count() + 1
+    }
+}
+
+pub(crate) fn get_colspan(cell: &Cell) -> usize {
+    cmp::max(cell.format().colspan.unwrap_or(1), 1)
+}
+
+fn get_rowspan(cell: &Cell) -> usize {
+    cmp::max(cell.format().rowspan.unwrap_or(1), 1)
+}
+
+/// First column of each cell of the rows, the columns covered by the cells spanning from the
+/// rows above being skipped. These covered columns are also returned for each row.
+#[allow(clippy::type_complexity)]
+fn place_rows<'c, 'a>(
+    rows: &'c [Vec<Cell<'a>>],
+) -> (Vec<Vec<(usize, &'c Cell<'a>)>>, Vec<Vec<usize>>) {
+    // number of rows still covered, for each column
+    let mut covering: Vec<usize> = vec![];
+    let mut placed = Vec::with_capacity(rows.len());
+    let mut covered = Vec::with_capacity(rows.len());
+    for row in rows {
+        let row_covered: Vec<usize> = (0..covering.len()).filter(|c| covering[*c] > 0).collect();
+        let mut col = 0;
+        let mut cells = Vec::with_capacity(row.len());
+        for cell in row {
+            while matches!(covering.get(col), Some(n) if *n > 0) {
+                col += 1;
+            }
+            cells.push((col, cell));
+            let span = get_colspan(cell);
+            if covering.len() < col + span {
+                covering.resize(col + span, 0);
+            }
+            for n in &mut covering[col..col + span] {
+                *n = get_rowspan(cell);
+            }
+            col += span;
+        }
+        for n in covering.iter_mut().filter(|n| **n > 0) {
+            *n -= 1;
+        }
+        placed.push(cells);
+        covered.push(row_covered);
+    }
+    (placed, covered)
+}
+
+#[cfg(feature = "ansi_term_style")]
+/// Paints each line of a text (styles do not span the lines once wrapped)
+fn paint_lines(style: ansi_term::Style, s: &str) -> String {
+    let lines: Vec<_> = s.split('\n').map(|l| style.paint(l).to_string()).collect();
+    lines.join("\n")
+}
+
+/// Rows of the transposed grid, the spans of the cells being swapped
+///
+/// Gaps left in the middle of the new rows are filled with empty cells.
+fn transpose_rows(rows: Vec<Vec<Cell>>) -> Vec<Vec<Cell>> {
+    let (placed, _) = place_rows(&rows);
+    let cols: Vec<Vec<usize>> = placed
+        .iter()
+        .map(|row| row.iter().map(|(col, _)| *col).collect())
+        .collect();
+    let col_nb = placed
+        .iter()
+        .flatten()
+        .map(|(col, cell)| col + get_colspan(cell))
+        .max()
+        .unwrap_or(0);
+    let row_nb = rows.len();
+    // cells of the new rows and positions covered by spanning cells
+    let mut grid: Vec<Vec<Option<Cell>>> = (0..col_nb)
+        .map(|_| (0..row_nb).map(|_| None).collect())
+        .collect();
+    let mut covered = vec![vec![false; row_nb]; col_nb];
+    for (r, (row, cols)) in rows.into_iter().zip(cols).enumerate() {
+        for (cell, col) in row.into_iter().zip(cols) {
+            let rows_end = cmp::min(r + get_rowspan(&cell), row_nb);
+            for covered_col in &mut covered[col..col + get_colspan(&cell)] {
+                for c in &mut covered_col[r..rows_end] {
+                    *c = true;
+                }
+            }
+            grid[col][r] = Some(cell.transposed());
+        }
+    }
+    grid.into_iter()
+        .zip(covered)
+        .map(|(cells, covered)| {
+            let end = cells.iter().rposition(Option::is_some).map_or(0, |r| r + 1);
+            cells
+                .into_iter()
+                .zip(covered)
+                .take(end)
+                .filter_map(|(cell, covered)| match cell {
+                    Some(cell) => Some(cell),
+                    None if covered => None,
+                    None => Some(Cell::from("")),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Splits the columns into blocks fitting in `width` (unless a single column is too wide),
+/// each of them starting with the first `keys` columns
+fn split_cols<F>(cols: &[usize], keys: usize, width: usize, table_width: F) -> Vec<Vec<usize>>
+where
+    F: Fn(&[usize]) -> usize,
+{
+    let (keys, others) = cols.split_at(cmp::min(keys, cols.len()));
+    let mut blocks = vec![];
+    let mut block = keys.to_vec();
+    for col in others {
+        block.push(*col);
+        if block.len() > keys.len() + 1 && table_width(&block) > width {
+            block.pop();
+            blocks.push(mem::replace(&mut block, keys.to_vec()));
+            block.push(*col);
+        }
+    }
+    if blocks.is_empty() || block.len() > keys.len() {
+        blocks.push(block);
+    }
+    blocks
+}
+
+/// Groups of consecutive rows, each of them as large as `size` allows (in lines, a row taking
+/// its `heights` plus its `lines` above unless it is the first of its group, each group
+/// `overhead` more and the last one `tail` more), only split before the `breakable` rows
+fn group_rows(
+    size: PageSize,
+    heights: &[usize],
+    lines: &[usize],
+    breakable: &[bool],
+    overhead: usize,
+    tail: usize,
+) -> Vec<Range<usize>> {
+    let nb = heights.len();
+    let (budget, heights, lines, tail) = match size {
+        PageSize::Rows(rows) => (rows, vec![1; nb], vec![0; nb], 0),
+        PageSize::Lines(size) => (
+            size.saturating_sub(overhead),
+            heights.to_vec(),
+            lines.to_vec(),
+            tail,
+        ),
+    };
+    let mut groups = vec![];
+    let mut start = 0;
+    while start < nb {
+        let mut end = start + 1;
+        let mut used = heights[start];
+        while end < nb && used + lines[end] + heights[end] <= budget {
+            used += lines[end] + heights[end];
+            end += 1;
+        }
+        // the tail comes after the last row
+        let last = end == nb;
+        while last && end > start + 1 && used + tail > budget {
+            end -= 1;
+            used -= lines[end] + heights[end];
+        }
+        if end < nb && !breakable[end] {
+            // back to the last break in the group, or forward to the next one
+            end = (start + 1..end)
+                .rev()
+                .chain(end..nb)
+                .find(|row| breakable[*row])
+                .unwrap_or(nb);
+        }
+        groups.push(start..end);
+        start = end;
+    }
+    groups
+}
+
+/// Shifts the placed cells (and the covered columns) one column right
+fn shift_cols(rows: &mut [Vec<(usize, &Cell)>], covered: &mut [Vec<usize>]) {
+    for (col, _) in rows.iter_mut().flatten() {
+        *col += 1;
+    }
+    for col in covered.iter_mut().flatten() {
+        *col += 1;
+    }
+}
+
+/// Cell alignment: cell override, then column alignment (Decimal only applies to numbers)
+fn get_cell_align(format: &CellFormat, col_align: &Align, cell: &Cell) -> Align {
+    match format.align {
+        Some(align) => align,
+        None if *col_align == Align::Decimal && !cell.is_a_number() => Align::Right,
+        None => *col_align,
+    }
+}
+
+/// Slots of a row, empty ones filling the columns neither with cells nor `covered` by the rows
+/// above (the row spans are limited to the `rows_left`)
+fn get_slots<'c>(
+    row: &[(usize, &'c Cell<'c>)],
+    covered: &[usize],
+    rows_left: usize,
+    col_nb: usize,
+    col_spec: &[(bool, usize)],
+    col_align: &[Align],
+    header: bool,
+) -> Vec<Slot<'c>> {
+    let mut slots = Vec::with_capacity(col_nb);
+    let empty = |cols: std::ops::Range<usize>| {
+        cols.filter(|col| !covered.contains(col))
+            .map(Slot::empty)
+            .collect::<Vec<_>>()
+    };
+    let mut next = 0;
+    for (col, cell) in row {
+        slots.extend(empty(next..*col));
+        let format = cell.format();
+        let (styled, unstyled) = match cell.content() {
+            Cell::Text(u) => (u.to_string(), u.unstyle()),
+            Cell::Table(table) => {
+                let s = table.tabulate();
+                let unstyled = AsciiEscapedString::from(&s).unstyle();
+                (s, unstyled)
+            }
+            _ => {
+                let s = cell.to_string_with_precision(col_spec[*col].1).unwrap();
+                (s.clone(), s)
+            }
+        };
+        let span = get_colspan(cell);
+        slots.push(Slot {
+            col: *col,
+            span,
+            rowspan: cmp::min(get_rowspan(cell), rows_left),
+            styled,
+            unstyled,
+            number: cell.is_a_number(),
+            header,
+            align: get_cell_align(&format, &col_align[*col], cell),
+            padding: format.padding.unwrap_or(0),
+            table: cell.to_table(),
+            guides: None,
+        });
+        next = col + span;
+    }
+    slots.extend(empty(next..col_nb));
+    slots
+}
+
+/// Slots of the rows of the contents or the footers (see `get_slots`)
+fn get_body_slots<'c>(
+    rows: &[Vec<(usize, &'c Cell<'c>)>],
+    covered: &[Vec<usize>],
+    rows_left: &[usize],
+    col_nb: usize,
+    col_spec: &[(bool, usize)],
+    col_align: &[Align],
+) -> Vec<Vec<Slot<'c>>> {
+    rows.iter()
+        .zip(covered)
+        .zip(rows_left)
+        .map(|((row, covered), rows_left)| {
+            get_slots(row, covered, *rows_left, col_nb, col_spec, col_align, false)
+        })
+        .collect()
+}
+
+/// Slots restricted to the visible columns (slots without any visible column are dropped),
+/// their texts being fitted in their width by `fit`
+fn place_slots<'s, F>(
+    slots: &'s [Slot],
+    visible: &[usize],
+    col_width: &[usize],
+    seps: &[usize],
+    fit: F,
+) -> Vec<Placed<'s>>
+where
+    F: Fn(&Slot, usize) -> Option<(String, String)>,
+{
+    slots
+        .iter()
+        .filter_map(|slot| {
+            let first = visible.iter().position(|col| *col >= slot.col)?;
+            let span = visible[first..]
+                .iter()
+                .take_while(|col| **col < slot.col + slot.span)
+                .count();
+            if span > 0 {
+                let width = col_width[first..first + span].iter().sum::<usize>()
+                    + seps[first..first + span - 1].iter().sum::<usize>();
+                let width_left = width.saturating_sub(2 * slot.padding);
+                let text = match &slot.guides {
+                    None => fit(slot, width_left),
+                    Some(guides) => {
+                        let width_left = width_left.saturating_sub(get_text_width(&guides.0));
+                        let fitted = fit(slot, width_left);
+                        let (styled, unstyled) = match &fitted {
+                            Some((styled, unstyled)) => (styled.as_str(), unstyled.as_str()),
+                            None => (slot.styled.as_str(), slot.unstyled.as_str()),
+                        };
+                        Some((add_guides(styled, guides), add_guides(unstyled, guides)))
+                    }
+                };
+                Some(Placed {
+                    first,
+                    span,
+                    width,
+                    slot,
+                    text,
+                })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Guides of the rows of a tree: the ones of their first line and of their other lines (none
+/// for the roots)
+fn get_guides(depths: &[usize], row_nb: usize) -> Vec<Option<(String, String)>> {
+    let depth_of = |row: usize| depths.get(row).copied().unwrap_or(0);
+    // a row at `depth` follows, before any row closer to the roots
+    let followed = |row: usize, depth: usize| {
+        (row + 1..row_nb).map(depth_of).find(|d| *d <= depth) == Some(depth)
+    };
+    (0..row_nb)
+        .map(|row| {
+            let depth = depth_of(row);
+            if depth == 0 {
+                return None;
+            }
+            let mut first = String::new();
+            for ancestor in 1..depth {
+                first.push_str(if followed(row, ancestor) {
+                    "│  "
+                } else {
+                    "   "
+                });
+            }
+            let mut others = first.clone();
+            if followed(row, depth) {
+                first.push_str("├─ ");
+                others.push_str("│  ");
+            } else {
+                first.push_str("└─ ");
+                others.push_str("   ");
+            }
+            Some((first, others))
+        })
+        .collect()
+}
+
+/// Prefixes the first line of `text` and its other lines with their guides
+fn add_guides(text: &str, (first, others): &(String, String)) -> String {
+    let lines = text.split('\n').enumerate();
+    let lines: Vec<String> = lines
+        .map(|(i, line)| match i {
+            0 => format!("{}{}", first, line),
+            _ => format!("{}{}", others, line),
+        })
+        .collect();
+    lines.join("\n")
+}
+
+/// Height of each row, the last row of a span growing if its cell is higher than the span
+fn get_heights(rows: &[Vec<Placed>]) -> Vec<usize> {
+    let mut heights: Vec<usize> = rows
+        .iter()
+        .map(|row| {
+            let cells = row.iter().filter(|placed| placed.slot.rowspan == 1);
+            cells.map(Placed::height).max().unwrap_or(1)
+        })
+        .collect();
+    for (i, row) in rows.iter().enumerate() {
+        for placed in row.iter().filter(|placed| placed.slot.rowspan > 1) {
+            let rows = i..i + placed.slot.rowspan;
+            let height: usize = heights[rows.clone()].iter().sum();
+            if placed.height() > height {
+                heights[rows.end - 1] += placed.height() - height;
+            }
+        }
+    }
+    heights
+}
+
+/// Cells of each row with their first line, the ones spanning from the rows above included
+fn stack_rows<'p, 's>(
+    rows: &'p [Vec<Placed<'s>>],
+    heights: &[usize],
+) -> Vec<Vec<(&'p Placed<'s>, usize)>> {
+    let mut spanning: Vec<(&Placed, usize, usize)> = vec![];
+    rows.iter()
+        .enumerate()
+        .map(|(i, row)| {
+            spanning.retain(|(_, end, _)| *end > i);
+            let mut cells = Vec::with_capacity(row.len() + spanning.len());
+            for (placed, _, line) in &mut spanning {
+                cells.push((*placed, *line));
+                *line += heights[i];
+            }
+            for placed in row {
+                if placed.slot.rowspan > 1 {
+                    spanning.push((placed, i + placed.slot.rowspan, heights[i]));
+                }
+                cells.push((placed, 0));
+            }
+            cells.sort_by_key(|(placed, _)| placed.first);
+            cells
+        })
+        .collect()
+}
+
+/// For each boundary between two visible columns, true if the row is split there
+fn get_splits(row: &[(&Placed, usize)], nb: usize) -> Vec<bool> {
+    let mut splits = vec![true; nb.saturating_sub(1)];
+    for (placed, _) in row {
+        for split in &mut splits[placed.first..placed.first + placed.span - 1] {
+            *split = false;
+        }
+    }
+    splits
+}
+
+/// For each visible column, false if a cell spans over the line above the row
+fn get_segments(row: &[(&Placed, usize)], nb: usize) -> Vec<bool> {
+    let mut segments = vec![true; nb];
+    for (placed, _) in row.iter().filter(|(_, line)| *line > 0) {
+        for segment in &mut segments[placed.first..placed.first + placed.span] {
+            *segment = false;
+        }
+    }
+    segments
+}
+
+fn get_col_width(col_nb: usize, slots: &[&Slot], col_max_width: &[Option<usize>]) -> Vec<usize> {
+    let mut col_width = vec![0; col_nb];
+    for slot in slots.iter().filter(|slot| slot.span == 1) {
+        let col = slot.col;
+        // numbers and nested tables are never cut
+        let width = match col_max_width[col] {
+            Some(max) if slot.is_cuttable() => {
+                // at least one character after the guides
+                let min_width = slot.guides_width() + 2 * slot.padding + 1;
+                cmp::min(slot.width(), cmp::max(max, min_width))
+            }
+            _ => slot.width(),
+        };
+        col_width[col] = cmp::max(width, col_width[col]);
+    }
+    col_width
+}
+
+/// Widens the `visible` columns under the spanning cells wider than them (the widths, `seps`
+/// between the columns, max widths and `fixed` being the ones of the visible columns)
+fn spread_spans(
+    col_width: &mut [usize],
+    slots: &[&Slot],
+    visible: &[usize],
+    seps: &[usize],
+    col_max_width: &[Option<usize>],
+    fixed: &[bool],
+) {
+    let mut spanning: Vec<&&Slot> = slots.iter().filter(|slot| slot.span > 1).collect();
+    spanning.sort_by_key(|slot| slot.span);
+    for slot in spanning {
+        // the slot only spreads over its visible columns
+        let first = match visible.iter().position(|col| *col >= slot.col) {
+            Some(first) => first,
+            None => continue,
+        };
+        let span = visible[first..]
+            .iter()
+            .take_while(|col| **col < slot.col + slot.span)
+            .count();
+        if span == 0 {
+            continue;
+        }
+        let cols = first..first + span;
+        let inner_seps = &seps[first..first + span - 1];
+        let mut width = slot.width();
+        // texts are cut if all the columns have a max width
+        let max: Option<usize> = col_max_width[cols.clone()].iter().copied().sum();
+        if let Some(max) = max {
+            if slot.is_cuttable() {
+                width = cmp::min(width, max + inner_seps.iter().sum::<usize>());
+            }
+        }
+        widen(
+            &mut col_width[cols.clone()],
+            width,
+            inner_seps,
+            &fixed[cols],
+        );
+    }
+}
+
+/// Widens the columns so that they are at least `width` wide, the separators between them
+/// included
+///
+/// The `fixed` columns are only widened if all of them are fixed.
+fn widen(col_width: &mut [usize], width: usize, seps: &[usize], fixed: &[bool]) {
+    let mut cols: Vec<usize> = (0..col_width.len()).filter(|col| !fixed[*col]).collect();
+    if cols.is_empty() {
+        cols = (0..col_width.len()).collect();
+    }
+    let span = cols.len();
+    if span == 0 {
+        return;
+    }
+    let available = col_width.iter().sum::<usize>() + seps.iter().sum::<usize>();
+    if width > available {
+        // the extra width is shared, the last columns getting the remainder
+        let extra = width - available;
+        for (i, col) in cols.iter().enumerate() {
+            col_width[*col] += extra / span + if span - i <= extra % span { 1 } else { 0 };
+        }
+    }
+}
+
+/// Values of the given columns
+fn select_cols<T: Copy>(values: &[T], cols: &[usize]) -> Vec<T> {
+    cols.iter().map(|col| values[*col]).collect()
+}
+
+/// Widths of the separators between the columns, depending on the rules after them
+fn get_sep_widths(row: &style::DataRow, rules: &[bool]) -> Vec<usize> {
+    let (sep, rule) = (wrap::width(&row.sep), wrap::width(&row.rule));
+    rules.iter().map(|r| if *r { rule } else { sep }).collect()
+}
+
+/// For each boundary between two of the given columns, true if a rule is drawn there (i.e.
+/// after one of the columns in between)
+fn get_rules(col_rule: &[bool], cols: &[usize]) -> Vec<bool> {
+    cols.windows(2)
+        .map(|pair| col_rule[pair[0]..pair[1]].iter().any(|r| *r))
+        .collect()
+}
+
+/// Total width of the table rows
+fn get_table_width(fmt: &style::TableFormat, col_width: &[usize], rules: &[bool]) -> usize {
+    let row = &fmt.datarow;
+    wrap::width(&row.begin)
+        + col_width.iter().sum::<usize>()
+        + get_sep_widths(row, rules).iter().sum::<usize>()
+        + wrap::width(&row.end)
+}
+
+/// Shrinks the widest columns with the lowest priority, one char at a time, down to their
+/// minimum width
+fn shrink_col_width(col_width: &mut [usize], min_width: &[usize], priority: &[i32], excess: usize) {
+    for _ in 0..excess {
+        let candidate = (0..col_width.len())
+            .filter(|col| col_width[*col] > min_width[*col])
+            .min_by_key(|col| (priority[*col], cmp::Reverse(col_width[*col])));
+        match candidate {
+            Some(col) => col_width[col] -= 1,
+            None => break,
+        }
+    }
+}
+
+/// Display width of the widest line of an unstyled text
+fn get_text_width(unstyled: &str) -> usize {
+    unstyled
+        .split('\n')
+        .map(UnicodeWidthStr::width)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Wraps or truncates the text if it is wider than `width`, None if it already fits
+///
+/// ASCII escape sequences are kept in the fitted text and closed at the end of each line.
+fn fit_text(
+    slot: &Slot,
+    width: usize,
+    overflow: Overflow,
+    marker: &str,
+) -> Option<(String, String)> {
+    if let Some(table) = slot.table {
+        // nested tables are never cut, but fill their cell
+        if get_text_width(&slot.unstyled) >= width {
+            return None;
+        }
+        let options = RenderOptions {
+            fill: Some(width),
+            ..Default::default()
+        };
+        let filled = table.render(&options).0.remove(0);
+        let unstyled = AsciiEscapedString::from(&filled).unstyle();
+        return Some((filled, unstyled));
+    }
+    if slot.number || get_text_width(&slot.unstyled) <= width {
+        return None;
+    }
+    let fitted = match overflow {
+        Overflow::Wrap => wrap::wrap(&slot.styled, width),
+        Overflow::Truncate => wrap::truncate(&slot.styled, width, marker),
+    };
+    if slot.styled == slot.unstyled {
+        Some((fitted.clone(), fitted))
+    } else {
+        let unstyled = AsciiEscapedString::from(&fitted).unstyle();
+        Some((fitted, unstyled))
+    }
+}
+
+fn get_col_specs<'r, I>(col_nb: usize, rows: I) -> Vec<(bool, usize)>
+where
+    I: Iterator<Item = &'r Vec<(usize, &'r Cell<'r>)>>,
+{
+    let mut col_spec = vec![(true, 0); col_nb];
+    for (col, cell) in rows.flatten() {
+        // spanning cells do not belong to a single column
+        if get_colspan(cell) > 1 {
+            continue;
+        }
+        let spec = &mut col_spec[*col];
+        if cell.is_a_number() {
+            spec.1 = cmp::max(spec.1, cell.digits_len());
+        } else {
+            spec.0 = false;
+        }
+    }
+    col_spec
+}
+
+/// Creates a horizontal line, its junctions depending on the splits of the rows around it
+///
+/// The line is only drawn in the columns with a segment, the others getting the borders of `row`.
+fn create_line(
+    line: &style::Line,
+    row: &style::DataRow,
+    col_width: &[usize],
+    rules: &[bool],
+    above: &[bool],
+    below: &[bool],
+    segments: &[bool],
+) -> String {
+    let mut s = match segments.first() {
+        Some(false) => row.begin.clone(),
+        _ => line.begin.clone(),
+    };
+    for (col, w) in col_width.iter().enumerate() {
+        if col > 0 {
+            let (junctions, sep) = if rules[col - 1] {
+                (&line.rule, &row.rule)
+            } else {
+                (&line.sep, &row.sep)
+            };
+            let sep_width = wrap::width(&junctions.cross);
+            let junction = match (segments[col - 1], segments[col]) {
+                (true, true) => match (above[col - 1], below[col - 1]) {
+                    (true, true) => junctions.cross.clone(),
+                    (false, true) => junctions.down.clone(),
+                    (true, false) => junctions.up.clone(),
+                    (false, false) => line.hline.repeat(sep_width),
+                },
+                (false, true) => junctions.right.clone(),
+                (true, false) => junctions.left.clone(),
+                (false, false) if above[col - 1] => sep.clone(),
+                (false, false) => " ".repeat(sep_width),
+            };
+            s.push_str(&junction);
+        }
+        if segments[col] {
+            s.push_str(&line.hline.repeat(*w));
+        } else {
+            s.push_str(&" ".repeat(*w));
+        }
+    }
+    match segments.last() {
+        Some(false) => s.push_str(&row.end),
+        _ => s.push_str(&line.end),
+    }
+    s.trim_end().to_string()
+}
+
+/// Creates a line of a row from the texts of its cells and their first column
+fn create_data_line(row: &style::DataRow, rules: &[bool], content: &[(usize, String)]) -> String {
+    let mut s = row.begin.clone();
+    for (i, (first, text)) in content.iter().enumerate() {
+        if i > 0 {
+            s.push_str(if rules[first - 1] {
+                &row.rule
+            } else {
+                &row.sep
+            });
+        }
+        s.push_str(text);
+    }
+    s.push_str(&row.end);
+    s.trim_end().to_string()
+}
+
+fn format_text(
+    styled: &str,
+    unstyled: &str,
+    line_idx: usize,
+    align: &Align,
+    width: usize,
+    padding: usize,
+) -> String {
+    if padding > 0 {
+        let pad = " ".repeat(padding);
+        return pad.clone()
+            + &format_text(
+                styled,
+                unstyled,
+                line_idx,
+                align,
+                width.saturating_sub(2 * padding),
+                0,
+            )
+            + &pad;
+    }
+    if let Some(unstyled_word) = unstyled.split('\n').nth(line_idx) {
+        let word = styled
+            .split('\n')
+            .nth(line_idx)
+            .expect("unstyled word can't have more \\n than styled one");
+        let width = width.saturating_sub(
+            UnicodeWidthStr::width(unstyled_word as &str) - unstyled_word.chars().count(),
+        );
+        let formatted = match align {
+            Align::Right => format!("{:>width$}", unstyled_word, width = width),
+            Align::Left => format!("{:<width$}", unstyled_word, width = width),
+            Align::Center => format!("{:^width$}", unstyled_word, width = width),
+            Align::Decimal => {
+                let mut out = format!("{:>width$}", unstyled_word, width = width);
+                if let Some(dot) = out.rfind('.') {
+                    if out[(dot + 1)..].bytes().all(|c| c == b'0') {
+                        out.replace_range(dot.., &" ".repeat(out.len() - dot));
+                    } else {
+                        for i in (dot + 1..out.len()).rev() {
+                            if out.as_bytes()[i] == b'0' {
+                                out.replace_range(i..i + 1, " ");
+                            }
+                        }
+                    }
+                }
+                out
+            }
+        };
+        if unstyled_word != word {
+            formatted.replace(unstyled_word, word)
+        } else {
+            formatted
+        }
+    } else {
+        " ".repeat(width)
+    }
+}
+
+/// Creates the lines of the rows, each one preceded by its line (except the first row) which
+/// is not drawn under the cells spanning from the row above
+fn create_rows(
+    row: &style::DataRow,
+    lines_above: &[Option<&style::Line>],
+    rows: &[Vec<(&Placed, usize)>],
+    heights: &[usize],
+    col_width: &[usize],
+    rules: &[bool],
+) -> Vec<String> {
+    let nb = col_width.len();
+    let mut lines = vec![];
+    for (i, cells) in rows.iter().enumerate() {
+        if let (Some(line), true) = (lines_above[i], i > 0) {
+            let above = get_splits(&rows[i - 1], nb);
+            let below = get_splits(cells, nb);
+            let segments = get_segments(cells, nb);
+            lines.push(create_line(
+                line, row, col_width, rules, &above, &below, &segments,
+            ));
+        }
+        lines.extend(create_data_lines(row, rules, cells, heights[i]));
+    }
+    lines
+}
+
+/// Creates the `height` lines of a row, each cell starting at the given line of its text
+fn create_data_lines(
+    row: &style::DataRow,
+    rules: &[bool],
+    cells: &[(&Placed, usize)],
+    height: usize,
+) -> Vec<String> {
+    let mut lines = Vec::with_capacity(height);
+    for i in 0..height {
+        let formatted: Vec<_> = cells
+            .iter()
+            .map(|(placed, first_line)| {
+                let (styled, unstyled) = placed.texts();
+                let slot = placed.slot;
+                let line_idx = first_line + i;
+                let text = format_text(
+                    styled,
+                    unstyled,
+                    line_idx,
+                    &slot.align,
+                    placed.width,
+                    slot.padding,
+                );
+                (placed.first, text)
+            })
+            .collect();
+        lines.push(create_data_line(row, rules, &formatted));
+    }
+    lines
+}
+
+// --------------------------- Tests ---------------------------
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn headerless(style: Style) -> Table<'static> {
+        Table::new(
+            style,
+            vec![
+                vec![Cell::from("spam"), Cell::Float(41.9999)],
+                vec![Cell::from("eggs"), Cell::Int(451)],
+            ],
+            None,
+        )
+    }
+
+    fn table(style: Style) -> Table<'static> {
+        Table::new(
+            style.clone(),
+            headerless(style).contents,
+            Some(Headers::from(vec!["strings", "numbers"])),
+        )
+    }
+
+    fn multiline_headerless(style: Style) -> Table<'static> {
+        let mut table = Table::new(
+            style,
+            vec![
+                vec![Cell::from("foo bar\nbaz\nbau"), Cell::from("hello")],
+                vec![Cell::from(""), Cell::from("multiline\nworld")],
+            ],
+            None,
+        );
+        table.set_align(Align::Center, Align::Right);
+        table
+    }
+
+    fn multiline(style: Style) -> Table<'static> {
+        Table::new(
+            style,
+            vec![vec![Cell::Int(2), Cell::from("foo\nbar")]],
+            Some(Headers::from(vec!["more\nspam eggs", "more spam\n& eggs"])),
+        )
+    }
+
+    fn multiline_empty_cells(style: Style) -> Table<'static> {
+        Table::new(
+            style.clone(),
+            vec![
+                vec![Cell::Int(1), Cell::from(""), Cell::from("")],
+                vec![
+                    Cell::Int(2),
+                    Cell::from("very long data"),
+                    Cell::from("fold\nthis"),
+                ],
+            ],
+            Some(Headers::from(vec!["hdr", "data", "fold"])),
+        )
+    }
+
+    fn multiline_empty_cells_headerless(style: Style) -> Table<'static> {
+        Table::new(
+            style,
+            vec![
+                vec![Cell::Int(0), Cell::from(""), Cell::from("")],
+                vec![Cell::Int(1), Cell::from(""), Cell::from("")],
+                vec![
+                    Cell::Int(2),
+                    Cell::from("very long data"),
+                    Cell::from("fold\nthis"),
+                ],
+            ],
+            None,
+        )
+    }
+
+    #[test]
+    fn plain() {
+        //Output: plain with headers
+        let result = table(Style::Plain).tabulate();
+        let expected = [
+            "strings      numbers",
+            "spam         41.9999",
+            "eggs        451",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn plain_headerless() {
+        //Output: plain without headers
+        let result = headerless(Style::Plain).tabulate();
+        let expected = ["spam   41.9999", "eggs  451"].join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn plain_multiline_headerless() {
+        //Output: plain with multiline cells without headers
+        let result = multiline_headerless(Style::Plain).tabulate();
+        let expected = [
+            "foo bar    hello",
+            "  baz",
+            "  bau",
+            "         multiline",
+            "           world",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn plain_multiline() {
+        //Output: plain with multiline cells with headers
+        let result = multiline(Style::Plain).tabulate();
+        let expected = [
+            "       more  more spam",
+            "  spam eggs  & eggs",
+            "          2  foo",
+            "             bar",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn plain_multiline_with_empty_cells() {
+        //Output: plain with multiline cells and empty cells with headers
+        let result = multiline_empty_cells(Style::Plain).tabulate();
+        let expected = [
+            "  hdr  data            fold",
+            "    1",
+            "    2  very long data  fold",
+            "                       this",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn plain_multiline_with_empty_cells_headerless() {
+        //Output: plain with multiline cells and empty cells without headers
+        let result = multiline_empty_cells_headerless(Style::Plain).tabulate();
+        let expected = [
+            "0",
+            "1",
+            "2  very long data  fold",
+            "                   this",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn simple() {
+        //Output: simple with headers
+        let result = table(Style::Simple).tabulate();
+        let expected = [
+            "strings      numbers",
+            "---------  ---------",
+            "spam         41.9999",
+            "eggs        451",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn simple_multiline_2() {
+        //Output: simple with multiline cells
+        let mut table = Table::new(
+            Style::Simple,
+            vec![
+                vec![Cell::from("foo"), Cell::from("bar")],
+                vec![Cell::from("spam"), Cell::from("multiline\nworld")],
+            ],
+            Some(Headers::from(vec!["key", "value"])),
+        );
+        table.set_align(Align::Center, Align::Right);
+        let result = table.tabulate();
+        let expected = [
+            " key     value",
+            "-----  ---------",
+            " foo      bar",
+            "spam   multiline",
+            "         world",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn simple_headerless() {
+        //Output: simple without headers
+        let result = headerless(Style::Simple).tabulate();
+        let expected = [
+            "----  --------",
+            "spam   41.9999",
+            "eggs  451",
+            "----  --------",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn simple_multiline_headerless() {
+        //Output: simple with multiline cells without headers
+        let result = multiline_headerless(Style::Simple).tabulate();
+        let expected = [
+            "-------  ---------",
+            "foo bar    hello",
+            "  baz",
+            "  bau",
+            "         multiline",
+            "           world",
+            "-------  ---------",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn simple_multiline() {
+        //Output: simple with multiline cells with headers
+        let result = multiline(Style::Simple).tabulate();
+        let expected = [
+            "       more  more spam",
+            "  spam eggs  & eggs",
+            "-----------  -----------",
+            "          2  foo",
+            "             bar",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn simple_multiline_ascii_escaped() {
+        //Output: simple with multiline headers and colors (ascii escape)
+        let mut headers = Headers::with_capacity(2);
+        headers
+            .push(AsciiEscapedString::from("more\nspam \x1b[31meggs\x1b[0m"))
+            .push(String::from("more spam\n& eggs"));
+        let result = Table::new(
+            Style::Simple,
+            vec![vec![Cell::Int(2), Cell::from("foo\nbar")]],
+            Some(headers),
+        )
+        .tabulate();
+        let expected = [
+            "       more  more spam",
+            "  spam \x1b[31meggs\x1b[0m  & eggs",
+            "-----------  -----------",
+            "          2  foo",
+            "             bar",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn simple_multiline_with_empty_cells() {
+        //Output: simple with multiline cells and empty cells with headers
+        let result = multiline_empty_cells(Style::Simple).tabulate();
+        let expected = [
+            "  hdr  data            fold",
+            "-----  --------------  ------",
+            "    1",
+            "    2  very long data  fold",
+            "                       this",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn simple_multiline_with_empty_cells_headerless() {
+        //Output: simple with multiline cells and empty cells without headers
+        let result = multiline_empty_cells_headerless(Style::Simple).tabulate();
+        let expected = [
+            "-  --------------  ----",
+            "0",
+            "1",
+            "2  very long data  fold",
+            "                   this",
+            "-  --------------  ----",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn github() {
+        //Output: github with headers
+        let result = table(Style::Github).tabulate();
+        let expected = [
+            "| strings   |   numbers |",
+            "|-----------|-----------|",
+            "| spam      |   41.9999 |",
+            "| eggs      |  451      |",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn grid() {
+        //Output: grid with headers
+        let result = table(Style::Grid).tabulate();
+        let expected = [
+            "+-----------+-----------+",
+            "| strings   |   numbers |",
+            "+===========+===========+",
+            "| spam      |   41.9999 |",
+            "+-----------+-----------+",
+            "| eggs      |  451      |",
+            "+-----------+-----------+",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    #[allow(clippy::approx_constant)]
+    fn grid_wide_characters() {
+        //Output: grid with wide characters in headers
+        let headers = Headers::from(vec!["strings", "配列"]);
+        let contents = vec![
+            vec![
+                Cell::from("Ответ на главный вопрос жизни, вселенной и всего такого"),
+                Cell::Int(42),
+            ],
+            vec![Cell::from("pi"), Cell::Float(3.1415)],
+        ];
+        let result = Table::new(Style::Grid, contents, Some(headers)).tabulate();
+        let expected = [
+            "+---------------------------------------------------------+---------+",
+            "| strings                                                 |    配列 |",
+            "+=========================================================+=========+",
+            "| Ответ на главный вопрос жизни, вселенной и всего такого | 42      |",
+            "+---------------------------------------------------------+---------+",
+            "| pi                                                      |  3.1415 |",
+            "+---------------------------------------------------------+---------+",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn issue_14() {
+        let headers = Headers::from(vec!["Hello", "World"]);
+        let contents = vec![vec![Cell::from("✔"), Cell::from("foo")]];
+
+        let result = Table::new(Style::Grid, contents, Some(headers)).tabulate();
+        let expected = [
+            "+---------+---------+",
+            "| Hello   | World   |",
+            "+=========+=========+",
+            "| ✔       | foo     |",
+            "+---------+---------+",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn grid_cell_format_overrides() {
+        //Output: grid with a centered cell, a padded cell and a right aligned column
+        let mut table = Table::new(
+            Style::Grid,
+            vec![
+                vec![Cell::from("spam"), Cell::Int(42), Cell::from("ok")],
+                vec![Cell::from("eggs"), Cell::Int(451), Cell::from("ok")],
+                vec![
+                    Cell::from("total").with_align(Align::Center),
+                    Cell::Int(493).with_padding(2),
+                    Cell::from("!").with_align(Align::Left),
+                ],
+            ],
+            Some(Headers::from(vec!["item", "qty", "status"])),
+        );
+        table.set_col_align(2, Align::Right);
+        let result = table.tabulate();
+        let expected = [
+            "+--------+---------+----------+",
+            "| item   |     qty |   status |",
+            "+========+=========+==========+",
+            "| spam   |      42 |       ok |",
+            "+--------+---------+----------+",
+            "| eggs   |     451 |       ok |",
+            "+--------+---------+----------+",
+            "| total  |   493   | !        |",
+            "+--------+---------+----------+",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn grid_max_width() {
+        //Output: grid with a long text wrapped and a wide column limited
+        let mut table = Table::new(
+            Style::Grid,
+            vec![
+                vec![
+                    Cell::from("3f2a"),
+                    Cell::from("Fix the width of wide characters in headers"),
+                ],
+                vec![
+                    Cell::from("9c1b"),
+                    Cell::Text(Box::new(AsciiEscapedString::from(
+                        "\x1b[31mRevert everything\x1b[0m",
+                    ))),
+                ],
+            ],
+            Some(Headers::from(vec!["commit", "message"])),
+        );
+        table.set_max_width(12);
+        table.set_col_max_width(0, 4);
+        let result = table.tabulate();
+        let expected = [
+            "+------+--------------+",
+            "| comm | message      |",
+            "| it   |              |",
+            "+======+==============+",
+            "| 3f2a | Fix the      |",
+            "|      | width of     |",
+            "|      | wide         |",
+            "|      | characters   |",
+            "|      | in headers   |",
+            "+------+--------------+",
+            "| 9c1b | \x1b[31mRevert\x1b[0m       |",
+            "|      | \x1b[31meverything\x1b[0m   |",
+            "+------+--------------+",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn fancy_grid_truncate() {
+        //Output: fancy grid with truncated texts
+        let mut table = Table::new(
+            Style::Fancy,
+            vec![
+                vec![Cell::from("web-01"), Cell::from("connection refused")],
+                vec![
+                    Cell::from("db-primary-eu-west"),
+                    Cell::Text(Box::new(AsciiEscapedString::from(
+                        "\x1b[32mhealthy and replicating\x1b[0m",
+                    ))),
+                ],
+            ],
+            Some(Headers::from(vec!["host", "status"])),
+        );
+        table.set_max_width(10);
+        table.set_overflow(Overflow::Truncate);
+        table.set_col_max_width(1, 12);
+        table.set_truncation_marker("...");
+        let result = table.tabulate();
+        let expected = [
+            "╒════════════╤══════════════╕",
+            "│ host       │ status       │",
+            "╞════════════╪══════════════╡",
+            "│ web-01     │ connectio... │",
+            "├────────────┼──────────────┤",
+            "│ db-prim... │ \x1b[32mhealthy a...\x1b[0m │",
+            "╘════════════╧══════════════╛",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn grid_to_width() {
+        //Output: grid fitted in 41 and 34 columns, the path having a higher priority
+        let mut table = Table::new(
+            Style::Grid,
+            vec![vec![
+                Cell::from("/var/log/syslog.1"),
+                Cell::from("rotated by logrotate"),
+                Cell::Int(1024),
+            ]],
+            Some(Headers::from(vec!["path", "comment", "size"])),
+        );
+        table.set_col_priority(0, 1);
+        table.set_col_overflow(1, Overflow::Truncate);
+        table.set_col_min_width(1, 8);
+        let expected = [
+            "+-------------------+----------+--------+",
+            "| path              | comment  |   size |",
+            "+===================+==========+========+",
+            "| /var/log/syslog.1 | rotated… |   1024 |",
+            "+-------------------+----------+--------+",
+        ]
+        .join("\n");
+        assert_eq!(expected, table.tabulate_to_width(41));
+        let expected = [
+            "+--------------+----------+------+",
+            "| path         | comment  | size |",
+            "+==============+==========+======+",
+            "| /var/log/sys | rotated… | 1024 |",
+            "| log.1        |          |      |",
+            "+--------------+----------+------+",
+        ]
+        .join("\n");
+        assert_eq!(expected, table.tabulate_to_width(34));
+    }
+
+    #[test]
+    fn simple_to_width_hiding_columns() {
+        //Output: simple fitted in 30 columns by hiding the columns with the lowest priority
+        let mut table = Table::new(
+            Style::Simple,
+            vec![
+                vec![
+                    Cell::from("nginx-7c5d"),
+                    Cell::from("Running"),
+                    Cell::Int(0),
+                    Cell::from("10.0.0.12"),
+                    Cell::from("node-1"),
+                ],
+                vec![
+                    Cell::from("redis-0"),
+                    Cell::from("Pending"),
+                    Cell::Int(3),
+                    Cell::from("<none>"),
+                    Cell::from("<none>"),
+                ],
+            ],
+            Some(Headers::from(vec![
+                "name", "status", "restarts", "ip", "node",
+            ])),
+        );
+        table.set_fit(Fit::HideColumns);
+        table.set_hidden_note(true);
+        table.set_col_priority(0, 2);
+        table.set_col_priority(1, 1);
+        table.set_col_priority(3, -1);
+        table.set_col_priority(4, 1);
+        let expected = [
+            "name        status    node",
+            "----------  --------  ------",
+            "nginx-7c5d  Running   node-1",
+            "redis-0     Pending   <none>",
+            "2 columns hidden",
+        ]
+        .join("\n");
+        assert_eq!(expected, table.tabulate_to_width(30));
+    }
+
+    #[test]
+    fn simple_hide_columns_with_index() {
+        //Output: simple with its index column kept while the data columns are hidden
+        let mut table = Table::new(
+            Style::Simple,
+            vec![
+                vec![Cell::from("spam"), Cell::Int(42), Cell::from("kitchen")],
+                vec![Cell::from("eggs"), Cell::Int(451), Cell::from("fridge")],
+            ],
+            Some(Headers::from(vec!["item", "qty", "location"])),
+        );
+        table.set_index(Index::FromOne);
+        table.set_fit(Fit::HideColumns);
+        table.set_col_priority(0, 2);
+        table.set_col_priority(1, 1);
+        table.set_col_priority(2, 1);
+        let result = table.tabulate_to_width(12);
+        let expected = ["    item", "--  ------", " 1  spam", " 2  eggs"].join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn grid_hidden_column_under_span() {
+        //Output: grid whose spanning cell only keeps its visible column, wide enough for it
+        let mut table = Table::new(
+            Style::Grid,
+            vec![
+                vec![
+                    Cell::from("a"),
+                    Cell::Int(1),
+                    Cell::from("a rather long description"),
+                ],
+                vec![Cell::from("b"), Cell::from("spam").with_colspan(2)],
+            ],
+            None,
+        );
+        table.set_fit(Fit::HideColumns);
+        let result = table.tabulate_to_width(30);
+        let expected = [
+            "+---+------+",
+            "| a |    1 |",
+            "+---+------+",
+            "| b | spam |",
+            "+---+------+",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    fn colspan(style: Style) -> Table<'static> {
+        let mut headers = Headers::new();
+        headers
+            .push(String::from("host"))
+            .push_span(String::from("latency (ms)"), 2);
+        Table::new(
+            style,
+            vec![
+                vec![Cell::from("web-01"), Cell::Int(12), Cell::Int(48)],
+                vec![
+                    Cell::from("db-01"),
+                    Cell::from("unreachable").with_colspan(2),
+                ],
+                vec![Cell::from("2 hosts, 1 down").with_colspan(3)],
+            ],
+            Some(headers),
+        )
+    }
+
+    #[test]
+    fn fancy_grid_colspan() {
+        //Output: fancy grid with cells spanning several columns
+        let result = colspan(Style::Fancy).tabulate();
+        let expected = [
+            "╒════════╤════════════════╕",
+            "│ host   │   latency (ms) │",
+            "╞════════╪═══════╤════════╡",
+            "│ web-01 │    12 │     48 │",
+            "├────────┼───────┴────────┤",
+            "│ db-01  │    unreachable │",
+            "├────────┴────────────────┤",
+            "│ 2 hosts, 1 down         │",
+            "╘═════════════════════════╛",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn simple_colspan() {
+        //Output: simple with cells spanning several columns
+        let result = colspan(Style::Simple).tabulate();
+        let expected = [
+            "host      latency (ms)",
+            "------  --------------",
+            "web-01      12      48",
+            "db-01      unreachable",
+            "2 hosts, 1 down",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn fancy_grid_title_and_caption() {
+        //Output: fancy grid with a title in its top border and a caption below it
+        let mut table = table(Style::Fancy);
+        table.set_title("Disk usage");
+        table.set_caption("source: df -h", Align::Center);
+        let result = table.tabulate();
+        let expected = [
+            "╒═ Disk usage ══════════╕",
+            "│ strings   │   numbers │",
+            "╞═══════════╪═══════════╡",
+            "│ spam      │   41.9999 │",
+            "├───────────┼───────────┤",
+            "│ eggs      │  451      │",
+            "╘═══════════╧═══════════╛",
+            "      source: df -h",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn grid_wide_title() {
+        //Output: grid widened by its title, with a caption wrapped to its width
+        let mut table = table(Style::Grid);
+        table.set_title("Strings and numbers, side by side");
+        table.set_caption("numbers are aligned on their decimal point", Align::Right);
+        let result = table.tabulate();
+        let expected = [
+            "+- Strings and numbers, side by side -+",
+            "| strings          |          numbers |",
+            "+==================+==================+",
+            "| spam             |          41.9999 |",
+            "+------------------+------------------+",
+            "| eggs             |         451      |",
+            "+------------------+------------------+",
+            "   numbers are aligned on their decimal",
+            "                                  point",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn simple_title() {
+        //Output: simple with a centered title as its line above is hidden
+        let mut table = table(Style::Simple);
+        table.set_title("Values");
+        let result = table.tabulate();
+        let expected = [
+            "       Values",
+            "strings      numbers",
+            "---------  ---------",
+            "spam         41.9999",
+            "eggs        451",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    fn footers(style: Style) -> Table<'static> {
+        let mut table = table(style);
+        table.push_footer(vec![Cell::from("total"), Cell::Float(492.9999)]);
+        table
+    }
+
+    #[test]
+    fn github_footers() {
+        //Output: github with a section and a footer row, without lines around them
+        let mut table = footers(Style::Github);
+        table.push_section("more");
+        table.push_row(vec![Cell::from("ham"), Cell::Int(3)]);
+        let result = table.tabulate();
+        let expected = [
+            "| strings   |   numbers |",
+            "|-----------|-----------|",
+            "| spam      |   41.9999 |",
+            "| eggs      |  451      |",
+            "| more                  |",
+            "| ham       |    3      |",
+            "| total     |  492.9999 |",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+        // a valid table: every line between pipes, a single delimiter row below the headers
+        let lines: Vec<&str> = result.split('\n').collect();
+        assert!(lines.iter().all(|line| line.starts_with('|')));
+        let delimiters: Vec<usize> = (0..lines.len())
+            .filter(|i| lines[*i].chars().all(|c| c == '|' || c == '-'))
+            .collect();
+        assert_eq!(vec![1], delimiters);
+    }
+
+    #[test]
+    fn fancy_grid_footers() {
+        //Output: fancy grid with a footer row
+        let result = footers(Style::Fancy).tabulate();
+        let expected = [
+            "╒═══════════╤═══════════╕",
+            "│ strings   │   numbers │",
+            "╞═══════════╪═══════════╡",
+            "│ spam      │   41.9999 │",
+            "├───────────┼───────────┤",
+            "│ eggs      │  451      │",
+            "╞═══════════╪═══════════╡",
+            "│ total     │  492.9999 │",
+            "╘═══════════╧═══════════╛",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn grid_footers() {
+        //Output: grid with footer rows
+        let mut table = footers(Style::Grid);
+        table.push_footer(vec![Cell::from("mean"), Cell::Float(246.49995)]);
+        let result = table.tabulate();
+        let expected = [
+            "+-----------+-----------+",
+            "| strings   |   numbers |",
+            "+===========+===========+",
+            "| spam      |  41.9999  |",
+            "+-----------+-----------+",
+            "| eggs      | 451       |",
+            "+===========+===========+",
+            "| total     | 492.9999  |",
+            "+-----------+-----------+",
+            "| mean      | 246.49995 |",
+            "+-----------+-----------+",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
     }
-    lines
-}
-
-// --------------------------- Tests ---------------------------
-
-#[cfg(test)]
-mod tests {
-
-    use super::*;
 
-    fn headerless(style: Style) -> Table<'static> {
-        Table::new(
-            style,
-            vec![
-                vec![Cell::from("spam"), Cell::Float(41.9999)],
-                vec![Cell::from("eggs"), Cell::Int(451)],
-            ],
-            None,
-        )
+    #[test]
+    fn simple_footers() {
+        //Output: simple with a footer row
+        let result = footers(Style::Simple).tabulate();
+        let expected = [
+            "strings      numbers",
+            "---------  ---------",
+            "spam         41.9999",
+            "eggs        451",
+            "---------  ---------",
+            "total       492.9999",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
     }
 
-    fn table(style: Style) -> Table<'static> {
-        Table::new(
-            style.clone(),
-            headerless(style).contents,
-            Some(Headers::from(vec!["strings", "numbers"])),
-        )
+    #[test]
+    fn fancy_grid_summary() {
+        //Output: fancy grid with summary rows below a footer row
+        let mut table = footers(Style::Fancy);
+        table.add_summary(&[Agg::Mean, Agg::Min, Agg::Count]);
+        let result = table.tabulate();
+        let expected = [
+            "╒═══════════╤═══════════╕",
+            "│ strings   │   numbers │",
+            "╞═══════════╪═══════════╡",
+            "│ spam      │   41.9999 │",
+            "├───────────┼───────────┤",
+            "│ eggs      │  451      │",
+            "╞═══════════╪═══════════╡",
+            "│ total     │  492.9999 │",
+            "├───────────┼───────────┤",
+            "│ mean      │  246.5    │",
+            "├───────────┼───────────┤",
+            "│ min       │   41.9999 │",
+            "├───────────┼───────────┤",
+            "│ count     │    2      │",
+            "╘═══════════╧═══════════╛",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
     }
 
-    fn multiline_headerless(style: Style) -> Table<'static> {
+    #[test]
+    fn simple_mean_of_integers() {
+        //Output: simple with the mean of a column of integers, keeping its decimal
         let mut table = Table::new(
-            style,
+            Style::Simple,
             vec![
-                vec![Cell::from("foo bar\nbaz\nbau"), Cell::from("hello")],
-                vec![Cell::from(""), Cell::from("multiline\nworld")],
+                vec![Cell::from("spam"), Cell::Int(1)],
+                vec![Cell::from("eggs"), Cell::Int(2)],
             ],
-            None,
+            Some(Headers::from(vec!["item", "qty"])),
         );
-        table.set_align(Align::Center, Align::Right);
+        table.add_summary(&[Agg::Sum, Agg::Mean]);
+        let result = table.tabulate();
+        let expected = [
+            "item      qty",
+            "------  -----",
+            "spam      1",
+            "eggs      2",
+            "------  -----",
+            "sum       3",
+            "mean      1.5",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    fn sections(style: Style) -> Table<'static> {
+        let mut table = Table::new(style, vec![], Some(Headers::from(vec!["host", "service"])));
+        table.push_section("production");
+        table.push_row(vec![
+            Cell::from("web-01").with_rowspan(3),
+            Cell::from("nginx"),
+        ]);
+        table.push_row(vec![Cell::from("sshd")]);
+        table.push_separator();
+        table.push_row(vec![Cell::from("db-01"), Cell::from("postgres")]);
+        table.push_section("staging");
+        table.push_row(vec![Cell::from("web-02"), Cell::from("nginx")]);
         table
     }
 
-    fn multiline(style: Style) -> Table<'static> {
-        Table::new(
-            style,
-            vec![vec![Cell::Int(2), Cell::from("foo\nbar")]],
-            Some(Headers::from(vec!["more\nspam eggs", "more spam\n& eggs"])),
-        )
+    #[test]
+    fn fancy_grid_sections() {
+        //Output: fancy grid with sections, the row span being limited to its section
+        let result = sections(Style::Fancy).tabulate();
+        let expected = [
+            "╒════════╤═══════════╕",
+            "│ host   │ service   │",
+            "╞════════╧═══════════╡",
+            "│ production         │",
+            "├────────┬───────────┤",
+            "│ web-01 │ nginx     │",
+            "│        ├───────────┤",
+            "│        │ sshd      │",
+            "├────────┼───────────┤",
+            "│ db-01  │ postgres  │",
+            "├────────┴───────────┤",
+            "│ staging            │",
+            "├────────┬───────────┤",
+            "│ web-02 │ nginx     │",
+            "╘════════╧═══════════╛",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
     }
 
-    fn multiline_empty_cells(style: Style) -> Table<'static> {
+    #[cfg(feature = "ansi_term_style")]
+    #[test]
+    fn plain_styled_sections() {
+        //Output: plain with bold section headings
+        let mut table = sections(Style::Plain);
+        table.set_section_style(ansi_term::Style::new().bold());
+        let result = table.tabulate();
+        let expected = [
+            "host    service",
+            "\u{1b}[1mproduction\u{1b}[0m",
+            "web-01  nginx",
+            "        sshd",
+            "db-01   postgres",
+            "\u{1b}[1mstaging\u{1b}[0m",
+            "web-02  nginx",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    fn grouped_headers(style: Style) -> Table<'static> {
+        let mut headers = Headers::new();
+        headers
+            .push_cell(Cell::from("host").with_rowspan(2))
+            .push_span(String::from("disk (MB/s)"), 2)
+            .push_span(String::from("network (MB/s)"), 2)
+            .set_level_align(Align::Center);
+        headers
+            .add_level()
+            .push(String::from("read"))
+            .push(String::from("write"))
+            .push(String::from("in"))
+            .push(String::from("out"));
         Table::new(
-            style.clone(),
+            style,
             vec![
-                vec![Cell::Int(1), Cell::from(""), Cell::from("")],
                 vec![
-                    Cell::Int(2),
-                    Cell::from("very long data"),
-                    Cell::from("fold\nthis"),
+                    Cell::from("web-01"),
+                    Cell::Int(12),
+                    Cell::Int(3),
+                    Cell::Int(140),
+                    Cell::Int(95),
+                ],
+                vec![
+                    Cell::from("db-01"),
+                    Cell::Int(230),
+                    Cell::Int(180),
+                    Cell::Int(8),
+                    Cell::Int(11),
                 ],
             ],
-            Some(Headers::from(vec!["hdr", "data", "fold"])),
+            Some(headers),
         )
     }
 
-    fn multiline_empty_cells_headerless(style: Style) -> Table<'static> {
+    #[test]
+    fn grid_grouped_headers() {
+        //Output: grid with two levels of headers
+        let result = grouped_headers(Style::Grid).tabulate();
+        let expected = [
+            "+--------+------------------+------------------+",
+            "|  host  |   disk (MB/s)    |  network (MB/s)  |",
+            "|        +--------+---------+--------+---------+",
+            "|        |   read |   write |     in |     out |",
+            "+========+========+=========+========+=========+",
+            "| web-01 |     12 |       3 |    140 |      95 |",
+            "+--------+--------+---------+--------+---------+",
+            "| db-01  |    230 |     180 |      8 |      11 |",
+            "+--------+--------+---------+--------+---------+",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn fancy_grid_grouped_headers() {
+        //Output: fancy grid with two levels of headers
+        let result = grouped_headers(Style::Fancy).tabulate();
+        let expected = [
+            "╒════════╤══════════════════╤══════════════════╕",
+            "│  host  │   disk (MB/s)    │  network (MB/s)  │",
+            "│        ├────────┬─────────┼────────┬─────────┤",
+            "│        │   read │   write │     in │     out │",
+            "╞════════╪════════╪═════════╪════════╪═════════╡",
+            "│ web-01 │     12 │       3 │    140 │      95 │",
+            "├────────┼────────┼─────────┼────────┼─────────┤",
+            "│ db-01  │    230 │     180 │      8 │      11 │",
+            "╘════════╧════════╧═════════╧════════╧═════════╛",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn simple_grouped_headers_without_lines() {
+        //Output: simple with two levels of headers and no line between them
+        let mut table = grouped_headers(Style::Simple);
+        table.set_header_lines(false);
+        let result = table.tabulate();
+        let expected = [
+            " host     disk (MB/s)     network (MB/s)",
+            "          read    write      in       out",
+            "------  ------  -------  ------  --------",
+            "web-01      12        3     140        95",
+            "db-01      230      180       8        11",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    fn rowspan(style: Style) -> Table<'static> {
+        let headers = Headers::from(vec!["host", "service", "port"]);
         Table::new(
             style,
             vec![
-                vec![Cell::Int(0), Cell::from(""), Cell::from("")],
-                vec![Cell::Int(1), Cell::from(""), Cell::from("")],
                 vec![
-                    Cell::Int(2),
-                    Cell::from("very long data"),
-                    Cell::from("fold\nthis"),
+                    Cell::from("web-01").with_rowspan(3),
+                    Cell::from("nginx"),
+                    Cell::Int(443),
+                ],
+                vec![Cell::from("sshd"), Cell::Int(22)],
+                vec![Cell::from("node\nexporter"), Cell::Int(9100)],
+                vec![
+                    Cell::from("db-01\n(replica)").with_rowspan(2),
+                    Cell::from("postgres"),
+                    Cell::Int(5432),
                 ],
+                vec![Cell::from("pgbouncer"), Cell::Int(6432)],
             ],
-            None,
+            Some(headers),
         )
     }
 
     #[test]
-    fn plain() {
-        //Output: plain with headers
-        let result = table(Style::Plain).tabulate();
-        let expected = vec![
-            "strings      numbers",
-            "spam         41.9999",
-            "eggs        451",
+    fn grid_rowspan() {
+        //Output: grid with cells spanning several rows
+        let result = rowspan(Style::Grid).tabulate();
+        let expected = [
+            "+-----------+-----------+--------+",
+            "| host      | service   |   port |",
+            "+===========+===========+========+",
+            "| web-01    | nginx     |    443 |",
+            "|           +-----------+--------+",
+            "|           | sshd      |     22 |",
+            "|           +-----------+--------+",
+            "|           | node      |   9100 |",
+            "|           | exporter  |        |",
+            "+-----------+-----------+--------+",
+            "| db-01     | postgres  |   5432 |",
+            "|           +-----------+--------+",
+            "| (replica) | pgbouncer |   6432 |",
+            "+-----------+-----------+--------+",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn fancy_grid_rowspan() {
+        //Output: fancy grid with cells spanning several rows side by side
+        let mut table = rowspan(Style::Fancy);
+        table.contents.push(vec![
+            Cell::from("cache-01").with_rowspan(2),
+            Cell::from("redis").with_rowspan(2),
+            Cell::Int(6379),
+        ]);
+        table.contents.push(vec![Cell::Int(6380)]);
+        let result = table.tabulate();
+        let expected = [
+            "╒═══════════╤═══════════╤════════╕",
+            "│ host      │ service   │   port │",
+            "╞═══════════╪═══════════╪════════╡",
+            "│ web-01    │ nginx     │    443 │",
+            "│           ├───────────┼────────┤",
+            "│           │ sshd      │     22 │",
+            "│           ├───────────┼────────┤",
+            "│           │ node      │   9100 │",
+            "│           │ exporter  │        │",
+            "├───────────┼───────────┼────────┤",
+            "│ db-01     │ postgres  │   5432 │",
+            "│           ├───────────┼────────┤",
+            "│ (replica) │ pgbouncer │   6432 │",
+            "├───────────┼───────────┼────────┤",
+            "│ cache-01  │ redis     │   6379 │",
+            "│           │           ├────────┤",
+            "│           │           │   6380 │",
+            "╘═══════════╧═══════════╧════════╛",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn grid_headerless() {
+        //Output: grid without headers
+        let result = headerless(Style::Grid).tabulate();
+        let expected = [
+            "+------+----------+",
+            "| spam |  41.9999 |",
+            "+------+----------+",
+            "| eggs | 451      |",
+            "+------+----------+",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn grid_multiline_headerless() {
+        //Output: grid with multiline cells without headers
+        let result = multiline_headerless(Style::Grid).tabulate();
+        let expected = [
+            "+---------+-----------+",
+            "| foo bar |   hello   |",
+            "|   baz   |           |",
+            "|   bau   |           |",
+            "+---------+-----------+",
+            "|         | multiline |",
+            "|         |   world   |",
+            "+---------+-----------+",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn grid_multiline() {
+        //Output: grid with multiline cells with headers
+        let result = multiline(Style::Grid).tabulate();
+        let expected = [
+            "+-------------+-------------+",
+            "|        more | more spam   |",
+            "|   spam eggs | & eggs      |",
+            "+=============+=============+",
+            "|           2 | foo         |",
+            "|             | bar         |",
+            "+-------------+-------------+",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn grid_multiline_with_empty_cells() {
+        //Output: grid with multiline cells and empty cells with headers
+        let result = multiline_empty_cells(Style::Grid).tabulate();
+        let expected = [
+            "+-------+----------------+--------+",
+            "|   hdr | data           | fold   |",
+            "+=======+================+========+",
+            "|     1 |                |        |",
+            "+-------+----------------+--------+",
+            "|     2 | very long data | fold   |",
+            "|       |                | this   |",
+            "+-------+----------------+--------+",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn grid_multiline_with_empty_cells_headerless() {
+        //Output: grid with multiline cells and empty cells without headers
+        let result = multiline_empty_cells_headerless(Style::Grid).tabulate();
+        let expected = [
+            "+---+----------------+------+",
+            "| 0 |                |      |",
+            "+---+----------------+------+",
+            "| 1 |                |      |",
+            "+---+----------------+------+",
+            "| 2 | very long data | fold |",
+            "|   |                | this |",
+            "+---+----------------+------+",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn fancy_grid() {
+        //Output: fancy_grid with headers
+        let result = table(Style::Fancy).tabulate();
+        let expected = [
+            "╒═══════════╤═══════════╕",
+            "│ strings   │   numbers │",
+            "╞═══════════╪═══════════╡",
+            "│ spam      │   41.9999 │",
+            "├───────────┼───────────┤",
+            "│ eggs      │  451      │",
+            "╘═══════════╧═══════════╛",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn fancy_grid_headerless() {
+        //Output: fancy_grid without headers
+        let result = headerless(Style::Fancy).tabulate();
+        let expected = [
+            "╒══════╤══════════╕",
+            "│ spam │  41.9999 │",
+            "├──────┼──────────┤",
+            "│ eggs │ 451      │",
+            "╘══════╧══════════╛",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn fancy_grid_multiline_headerless() {
+        //Output: fancy_grid with multiline cells without headers
+        let result = multiline_headerless(Style::Fancy).tabulate();
+        let expected = [
+            "╒═════════╤═══════════╕",
+            "│ foo bar │   hello   │",
+            "│   baz   │           │",
+            "│   bau   │           │",
+            "├─────────┼───────────┤",
+            "│         │ multiline │",
+            "│         │   world   │",
+            "╘═════════╧═══════════╛",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn fancy_grid_multiline() {
+        //Output: fancy_grid with multiline cells with headers
+        let result = multiline(Style::Fancy).tabulate();
+        let expected = [
+            "╒═════════════╤═════════════╕",
+            "│        more │ more spam   │",
+            "│   spam eggs │ & eggs      │",
+            "╞═════════════╪═════════════╡",
+            "│           2 │ foo         │",
+            "│             │ bar         │",
+            "╘═════════════╧═════════════╛",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn plain_headerless() {
-        //Output: plain without headers
-        let result = headerless(Style::Plain).tabulate();
-        let expected = vec!["spam   41.9999", "eggs  451"].join("\n");
+    fn fancy_grid_multiline_with_empty_cells() {
+        //Output: fancy_grid with multiline cells and empty cells with headers
+        let result = multiline_empty_cells(Style::Fancy).tabulate();
+        let expected = [
+            "╒═══════╤════════════════╤════════╕",
+            "│   hdr │ data           │ fold   │",
+            "╞═══════╪════════════════╪════════╡",
+            "│     1 │                │        │",
+            "├───────┼────────────────┼────────┤",
+            "│     2 │ very long data │ fold   │",
+            "│       │                │ this   │",
+            "╘═══════╧════════════════╧════════╛",
+        ]
+        .join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn plain_multiline_headerless() {
-        //Output: plain with multiline cells without headers
-        let result = multiline_headerless(Style::Plain).tabulate();
-        let expected = vec![
-            "foo bar    hello",
-            "  baz",
-            "  bau",
-            "         multiline",
-            "           world",
+    fn fancy_grid_multiline_with_empty_cells_headerless() {
+        //Output: fancy_grid with multiline cells and empty cells without headers
+        let result = multiline_empty_cells_headerless(Style::Fancy).tabulate();
+        let expected = [
+            "╒═══╤════════════════╤══════╕",
+            "│ 0 │                │      │",
+            "├───┼────────────────┼──────┤",
+            "│ 1 │                │      │",
+            "├───┼────────────────┼──────┤",
+            "│ 2 │ very long data │ fold │",
+            "│   │                │ this │",
+            "╘═══╧════════════════╧══════╛",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn plain_multiline() {
-        //Output: plain with multiline cells with headers
-        let result = multiline(Style::Plain).tabulate();
-        let expected = vec![
-            "       more  more spam",
-            "  spam eggs  & eggs",
-            "          2  foo",
-            "             bar",
+    fn presto() {
+        //Output: presto with headers
+        let result = table(Style::Presto).tabulate();
+        let expected = [
+            " strings   |   numbers",
+            "-----------+-----------",
+            " spam      |   41.9999",
+            " eggs      |  451",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn plain_multiline_with_empty_cells() {
-        //Output: plain with multiline cells and empty cells with headers
-        let result = multiline_empty_cells(Style::Plain).tabulate();
-        let expected = vec![
-            "  hdr  data            fold",
-            "    1",
-            "    2  very long data  fold",
-            "                       this",
+    fn presto_headerless() {
+        //Output: presto without headers
+        let result = headerless(Style::Presto).tabulate();
+        let expected = [" spam |  41.9999", " eggs | 451"].join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn presto_multiline_headerless() {
+        //Output: presto with multiline cells without headers
+        let result = multiline_headerless(Style::Presto).tabulate();
+        let expected = [
+            " foo bar |   hello",
+            "   baz   |",
+            "   bau   |",
+            "         | multiline",
+            "         |   world",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn plain_multiline_with_empty_cells_headerless() {
-        //Output: plain with multiline cells and empty cells without headers
-        let result = multiline_empty_cells_headerless(Style::Plain).tabulate();
-        let expected = vec![
-            "0",
-            "1",
-            "2  very long data  fold",
-            "                   this",
+    fn presto_multiline() {
+        //Output: presto with multiline cells with headers
+        let result = multiline(Style::Presto).tabulate();
+        let expected = [
+            "        more | more spam",
+            "   spam eggs | & eggs",
+            "-------------+-------------",
+            "           2 | foo",
+            "             | bar",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn simple() {
-        //Output: simple with headers
-        let result = table(Style::Simple).tabulate();
-        let expected = vec![
-            "strings      numbers",
-            "---------  ---------",
-            "spam         41.9999",
-            "eggs        451",
+    fn presto_multiline_with_empty_cells() {
+        //Output: presto with multiline cells and empty cells with headers
+        let result = multiline_empty_cells(Style::Presto).tabulate();
+        let expected = [
+            "   hdr | data           | fold",
+            "-------+----------------+--------",
+            "     1 |                |",
+            "     2 | very long data | fold",
+            "       |                | this",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn simple_multiline_2() {
-        //Output: simple with multiline cells
-        let mut table = Table::new(
-            Style::Simple,
-            vec![
-                vec![Cell::from("foo"), Cell::from("bar")],
-                vec![Cell::from("spam"), Cell::from("multiline\nworld")],
-            ],
-            Some(Headers::from(vec!["key", "value"])),
-        );
-        table.set_align(Align::Center, Align::Right);
-        let result = table.tabulate();
-        let expected = vec![
-            " key     value",
-            "-----  ---------",
-            " foo      bar",
-            "spam   multiline",
-            "         world",
+    fn presto_multiline_with_empty_cells_headerless() {
+        //Output: presto with multiline cells and empty cells without headers
+        let result = multiline_empty_cells_headerless(Style::Presto).tabulate();
+        let expected = [
+            " 0 |                |",
+            " 1 |                |",
+            " 2 | very long data | fold",
+            "   |                | this",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn simple_headerless() {
-        //Output: simple without headers
-        let result = headerless(Style::Simple).tabulate();
-        let expected = vec![
-            "----  --------",
-            "spam   41.9999",
-            "eggs  451",
-            "----  --------",
+    fn fancygithub_grid() {
+        let result = table(Style::FancyGithub).tabulate();
+        let expected = [
+            "│ strings   │   numbers │",
+            "├───────────┼───────────┤",
+            "│ spam      │   41.9999 │",
+            "│ eggs      │  451      │",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn simple_multiline_headerless() {
-        //Output: simple with multiline cells without headers
-        let result = multiline_headerless(Style::Simple).tabulate();
-        let expected = vec![
-            "-------  ---------",
-            "foo bar    hello",
-            "  baz",
-            "  bau",
-            "         multiline",
-            "           world",
-            "-------  ---------",
+    fn fancygithub_grid_headerless() {
+        let result = headerless(Style::FancyGithub).tabulate();
+        let expected = ["│ spam │  41.9999 │", "│ eggs │ 451      │"].join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn fancygithub_grid_multiline_headerless() {
+        let result = multiline_headerless(Style::FancyGithub).tabulate();
+        let expected = [
+            "│ foo bar │   hello   │",
+            "│   baz   │           │",
+            "│   bau   │           │",
+            "│         │ multiline │",
+            "│         │   world   │",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn simple_multiline() {
-        //Output: simple with multiline cells with headers
-        let result = multiline(Style::Simple).tabulate();
-        let expected = vec![
-            "       more  more spam",
-            "  spam eggs  & eggs",
-            "-----------  -----------",
-            "          2  foo",
-            "             bar",
+    fn fancygithub_grid_multiline() {
+        let result = multiline(Style::FancyGithub).tabulate();
+        let expected = [
+            "│        more │ more spam   │",
+            "│   spam eggs │ & eggs      │",
+            "├─────────────┼─────────────┤",
+            "│           2 │ foo         │",
+            "│             │ bar         │",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn simple_multiline_ascii_escaped() {
-        //Output: simple with multiline headers and colors (ascii escape)
-        let mut headers = Headers::with_capacity(2);
-        headers
-            .push(AsciiEscapedString::from("more\nspam \x1b[31meggs\x1b[0m"))
-            .push(String::from("more spam\n& eggs"));
-        let result = Table::new(
-            Style::Simple,
-            vec![vec![Cell::Int(2), Cell::from("foo\nbar")]],
-            Some(headers),
-        )
-        .tabulate();
-        let expected = vec![
-            "       more  more spam",
-            "  spam \x1b[31meggs\x1b[0m  & eggs",
-            "-----------  -----------",
-            "          2  foo",
-            "             bar",
+    fn fancygithub_grid_multiline_with_empty_cells() {
+        let result = multiline_empty_cells(Style::FancyGithub).tabulate();
+        let expected = [
+            "│   hdr │ data           │ fold   │",
+            "├───────┼────────────────┼────────┤",
+            "│     1 │                │        │",
+            "│     2 │ very long data │ fold   │",
+            "│       │                │ this   │",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn simple_multiline_with_empty_cells() {
-        //Output: simple with multiline cells and empty cells with headers
-        let result = multiline_empty_cells(Style::Simple).tabulate();
-        let expected = vec![
-            "  hdr  data            fold",
-            "-----  --------------  ------",
-            "    1",
-            "    2  very long data  fold",
-            "                       this",
+    fn fancygithub_grid_multiline_with_empty_cells_headerless() {
+        let result = multiline_empty_cells_headerless(Style::FancyGithub).tabulate();
+        let expected = [
+            "│ 0 │                │      │",
+            "│ 1 │                │      │",
+            "│ 2 │ very long data │ fold │",
+            "│   │                │ this │",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn simple_multiline_with_empty_cells_headerless() {
-        //Output: simple with multiline cells and empty cells without headers
-        let result = multiline_empty_cells_headerless(Style::Simple).tabulate();
-        let expected = vec![
-            "-  --------------  ----",
-            "0",
-            "1",
-            "2  very long data  fold",
-            "                   this",
-            "-  --------------  ----",
+    fn fancypresto_grid() {
+        let result = table(Style::FancyPresto).tabulate();
+        let expected = [
+            "strings   │   numbers",
+            "──────────┼──────────",
+            "spam      │   41.9999",
+            "eggs      │  451",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn github() {
-        //Output: github with headers
-        let result = table(Style::Github).tabulate();
-        let expected = vec![
-            "| strings   |   numbers |",
-            "|-----------|-----------|",
-            "| spam      |   41.9999 |",
-            "| eggs      |  451      |",
+    fn fancypresto_grid_headerless() {
+        let result = headerless(Style::FancyPresto).tabulate();
+        let expected = ["spam │  41.9999", "eggs │ 451"].join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn fancypresto_grid_multiline_headerless() {
+        let result = multiline_headerless(Style::FancyPresto).tabulate();
+        let expected = [
+            "foo bar │   hello",
+            "  baz   │",
+            "  bau   │",
+            "        │ multiline",
+            "        │   world",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn grid() {
-        //Output: grid with headers
-        let result = table(Style::Grid).tabulate();
-        let expected = vec![
-            "+-----------+-----------+",
-            "| strings   |   numbers |",
-            "+===========+===========+",
-            "| spam      |   41.9999 |",
-            "+-----------+-----------+",
-            "| eggs      |  451      |",
-            "+-----------+-----------+",
+    fn fancypresto_grid_multiline() {
+        let result = multiline(Style::FancyPresto).tabulate();
+        let expected = [
+            "       more │ more spam",
+            "  spam eggs │ & eggs",
+            "────────────┼────────────",
+            "          2 │ foo",
+            "            │ bar",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn grid_wide_characters() {
-        //Output: grid with wide characters in headers
-        let headers = Headers::from(vec!["strings", "配列"]);
-        let contents = vec![
-            vec![
-                Cell::from("Ответ на главный вопрос жизни, вселенной и всего такого"),
-                Cell::Int(42),
-            ],
-            vec![Cell::from("pi"), Cell::Float(3.1415)],
-        ];
-        let result = Table::new(Style::Grid, contents, Some(headers)).tabulate();
-        let expected = vec![
-            "+---------------------------------------------------------+---------+",
-            "| strings                                                 |    配列 |",
-            "+=========================================================+=========+",
-            "| Ответ на главный вопрос жизни, вселенной и всего такого | 42      |",
-            "+---------------------------------------------------------+---------+",
-            "| pi                                                      |  3.1415 |",
-            "+---------------------------------------------------------+---------+",
+    fn fancypresto_grid_multiline_with_empty_cells() {
+        let result = multiline_empty_cells(Style::FancyPresto).tabulate();
+        let expected = [
+            "  hdr │ data           │ fold",
+            "──────┼────────────────┼───────",
+            "    1 │                │",
+            "    2 │ very long data │ fold",
+            "      │                │ this",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn issue_14() {
-        let headers = Headers::from(vec!["Hello", "World"]);
-        let contents = vec![vec![Cell::from("✔"), Cell::from("foo")]];
-
-        let result = Table::new(Style::Grid, contents, Some(headers)).tabulate();
-        let expected = vec![
-            "+---------+---------+",
-            "| Hello   | World   |",
-            "+=========+=========+",
-            "| ✔       | foo     |",
-            "+---------+---------+",
+    fn fancypresto_grid_multiline_with_empty_cells_headerless() {
+        let result = multiline_empty_cells_headerless(Style::FancyPresto).tabulate();
+        let expected = [
+            "0 │                │",
+            "1 │                │",
+            "2 │ very long data │ fold",
+            "  │                │ this",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
+    #[cfg(feature = "ansi_term_style")]
     #[test]
-    fn grid_headerless() {
-        //Output: grid without headers
-        let result = headerless(Style::Grid).tabulate();
-        let expected = vec![
-            "+------+----------+",
-            "| spam |  41.9999 |",
-            "+------+----------+",
-            "| eggs | 451      |",
-            "+------+----------+",
+    fn ansi_term_colored_content() {
+        use ansi_term::Colour::Red;
+        use ansi_term::{ANSIString, ANSIStrings};
+
+        let some_value = format!("{:b}", 42);
+        let strings: &[ANSIString] =
+            &[Red.paint("["), Red.bold().paint(some_value), Red.paint("]")];
+
+        let result = Table::new(
+            Style::Grid,
+            vec![vec![
+                Cell::Int(42),
+                Cell::Text(Box::new(ANSIStrings(strings))),
+            ]],
+            Some(Headers::from(vec!["Int", "Colored binary"])),
+        )
+        .tabulate();
+
+        let expected = [
+            "+-------+------------------+",
+            "|   Int | Colored binary   |",
+            "+=======+==================+",
+            "|    42 | \u{1b}[31m[\u{1b}[1m101010\u{1b}[0m\u{1b}[31m]\u{1b}[0m         |",
+            "+-------+------------------+",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
+    #[cfg(feature = "ansi_term_style")]
     #[test]
-    fn grid_multiline_headerless() {
-        //Output: grid with multiline cells without headers
-        let result = multiline_headerless(Style::Grid).tabulate();
-        let expected = vec![
-            "+---------+-----------+",
-            "| foo bar |   hello   |",
-            "|   baz   |           |",
-            "|   bau   |           |",
-            "+---------+-----------+",
-            "|         | multiline |",
-            "|         |   world   |",
-            "+---------+-----------+",
-        ]
-        .join("\n");
+    fn ansi_styled_borders() {
+        let mut table = table(Style::Fancy);
+        table.set_border_style(ansi_term::Color::Green.bold());
+        let result = table.tabulate();
+        let expected = [
+            "\u{1b}[1;32m╒═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═╤═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═╕\u{1b}[0m",
+            "\u{1b}[1;32m│ \u{1b}[0mstrings  \u{1b}[1;32m │ \u{1b}[0m  numbers\u{1b}[1;32m │\u{1b}[0m",
+            "\u{1b}[1;32m╞═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═╪═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═╡\u{1b}[0m",
+            "\u{1b}[1;32m│ \u{1b}[0mspam     \u{1b}[1;32m │ \u{1b}[0m  41.9999\u{1b}[1;32m │\u{1b}[0m",
+            "\u{1b}[1;32m├─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─┼─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─┤\u{1b}[0m",
+            "\u{1b}[1;32m│ \u{1b}[0meggs     \u{1b}[1;32m │ \u{1b}[0m 451     \u{1b}[1;32m │\u{1b}[0m",
+            "\u{1b}[1;32m╘═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═╧═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═╛\u{1b}[0m"
+        ].join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn grid_multiline() {
-        //Output: grid with multiline cells with headers
-        let result = multiline(Style::Grid).tabulate();
-        let expected = vec![
-            "+-------------+-------------+",
-            "|        more | more spam   |",
-            "|   spam eggs | & eggs      |",
-            "+=============+=============+",
-            "|           2 | foo         |",
-            "|             | bar         |",
-            "+-------------+-------------+",
+    fn fancy_grid_index() {
+        //Output: fancy grid with an index column, a rule after it and a footer
+        let mut table = footers(Style::Fancy);
+        table.set_index(Index::FromZero);
+        table.set_index_header("#");
+        table.set_index_rule(true);
+        let result = table.tabulate();
+        let expected = [
+            "╒═════╦═══════════╤═══════════╕",
+            "│   # ║ strings   │   numbers │",
+            "╞═════╬═══════════╪═══════════╡",
+            "│   0 ║ spam      │   41.9999 │",
+            "├─────╫───────────┼───────────┤",
+            "│   1 ║ eggs      │  451      │",
+            "╞═════╬═══════════╪═══════════╡",
+            "│     ║ total     │  492.9999 │",
+            "╘═════╩═══════════╧═══════════╛",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn grid_multiline_with_empty_cells() {
-        //Output: grid with multiline cells and empty cells with headers
-        let result = multiline_empty_cells(Style::Grid).tabulate();
-        let expected = vec![
-            "+-------+----------------+--------+",
-            "|   hdr | data           | fold   |",
-            "+=======+================+========+",
-            "|     1 |                |        |",
-            "+-------+----------------+--------+",
-            "|     2 | very long data | fold   |",
-            "|       |                | this   |",
-            "+-------+----------------+--------+",
+    fn grid_grouped_headers_index() {
+        //Output: grid with labels in the index column, spanning the levels of headers
+        let mut table = grouped_headers(Style::Grid);
+        table.set_index(Index::Labels(vec![String::from("a")]));
+        table.set_index_header("rack");
+        table.set_index_align(Align::Center);
+        table.set_index_rule(true);
+        table.set_col_align(0, Align::Right);
+        let result = table.tabulate();
+        let expected = [
+            "+--------++--------+------------------+------------------+",
+            "|  rack  ||  host  |   disk (MB/s)    |  network (MB/s)  |",
+            "|        ||        +--------+---------+--------+---------+",
+            "|        ||        |   read |   write |     in |     out |",
+            "+========++========+========+=========+========+=========+",
+            "|   a    || web-01 |     12 |       3 |    140 |      95 |",
+            "+--------++--------+--------+---------+--------+---------+",
+            "|        ||  db-01 |    230 |     180 |      8 |      11 |",
+            "+--------++--------+--------+---------+--------+---------+",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn grid_multiline_with_empty_cells_headerless() {
-        //Output: grid with multiline cells and empty cells without headers
-        let result = multiline_empty_cells_headerless(Style::Grid).tabulate();
-        let expected = vec![
-            "+---+----------------+------+",
-            "| 0 |                |      |",
-            "+---+----------------+------+",
-            "| 1 |                |      |",
-            "+---+----------------+------+",
-            "| 2 | very long data | fold |",
-            "|   |                | this |",
-            "+---+----------------+------+",
+    fn presto_index_summary() {
+        //Output: presto with an index column, the summary rows labelled after it
+        let mut table = table(Style::Presto);
+        table.set_index(Index::FromOne);
+        table.set_index_rule(true);
+        table.add_summary(&[Agg::Sum]);
+        let result = table.tabulate();
+        let expected = [
+            "    || strings   |   numbers",
+            "----++-----------+-----------",
+            "  1 || spam      |   41.9999",
+            "  2 || eggs      |  451",
+            "----++-----------+-----------",
+            "    || sum       |  492.9999",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
+    fn stub(style: Style) -> Table<'static> {
+        let mut table = Table::new(
+            style,
+            vec![
+                vec![Cell::Int(2020), Cell::Float(1.5), Cell::Int(-3)],
+                vec![Cell::Int(2021), Cell::Float(12.25), Cell::Int(7)],
+            ],
+            Some(Headers::from(vec!["year", "growth", "delta"])),
+        );
+        table.set_stub(true);
+        table
+    }
+
     #[test]
-    fn fancy_grid() {
-        //Output: fancy_grid with headers
-        let result = table(Style::Fancy).tabulate();
-        let expected = vec![
-            "╒═══════════╤═══════════╕",
-            "│ strings   │   numbers │",
-            "╞═══════════╪═══════════╡",
-            "│ spam      │   41.9999 │",
-            "├───────────┼───────────┤",
-            "│ eggs      │  451      │",
-            "╘═══════════╧═══════════╛",
+    fn grid_stub_index_summary() {
+        //Output: grid with an index, a stub column and a summary labelled in the stub
+        let mut table = stub(Style::Grid);
+        table.set_index(Index::FromOne);
+        table.add_summary(&[Agg::Max]);
+        let result = table.tabulate();
+        let expected = [
+            "+----+--------++----------+---------+",
+            "|    | year   ||   growth |   delta |",
+            "+====+========++==========+=========+",
+            "|  1 | 2020   ||     1.5  |      -3 |",
+            "+----+--------++----------+---------+",
+            "|  2 | 2021   ||    12.25 |       7 |",
+            "+====+========++==========+=========+",
+            "|    | max    ||    12.25 |       7 |",
+            "+----+--------++----------+---------+",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn fancy_grid_headerless() {
-        //Output: fancy_grid without headers
-        let result = headerless(Style::Fancy).tabulate();
-        let expected = vec![
-            "╒══════╤══════════╕",
-            "│ spam │  41.9999 │",
-            "├──────┼──────────┤",
-            "│ eggs │ 451      │",
-            "╘══════╧══════════╛",
+    fn simple_stub_summary() {
+        //Output: simple with a stub column labelling the summary rows
+        let mut table = stub(Style::Simple);
+        table.add_summary(&[Agg::Sum, Agg::Mean]);
+        let result = table.tabulate();
+        let expected = [
+            "year   |   growth    delta",
+            "-------+---------  -------",
+            "2020   |     1.5      -3",
+            "2021   |    12.25      7",
+            "-------+---------  -------",
+            "sum    |    13.75      4",
+            "mean   |     6.88      2",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
+    #[cfg(feature = "ansi_term_style")]
     #[test]
-    fn fancy_grid_multiline_headerless() {
-        //Output: fancy_grid with multiline cells without headers
-        let result = multiline_headerless(Style::Fancy).tabulate();
-        let expected = vec![
-            "╒═════════╤═══════════╕",
-            "│ foo bar │   hello   │",
-            "│   baz   │           │",
-            "│   bau   │           │",
-            "├─────────┼───────────┤",
-            "│         │ multiline │",
-            "│         │   world   │",
-            "╘═════════╧═══════════╛",
+    fn fancy_presto_styled_stub() {
+        //Output: fancy presto with an italic stub column
+        let mut table = stub(Style::FancyPresto);
+        table.set_stub_style(ansi_term::Style::new().italic());
+        let result = table.tabulate();
+        let expected = [
+            "year   ║   growth │   delta",
+            "───────╫──────────┼────────",
+            "\u{1b}[3m2020\u{1b}[0m   ║     1.5  │      -3",
+            "\u{1b}[3m2021\u{1b}[0m   ║    12.25 │       7",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn fancy_grid_multiline() {
-        //Output: fancy_grid with multiline cells with headers
-        let result = multiline(Style::Fancy).tabulate();
-        let expected = vec![
-            "╒═════════════╤═════════════╕",
-            "│        more │ more spam   │",
-            "│   spam eggs │ & eggs      │",
-            "╞═════════════╪═════════════╡",
-            "│           2 │ foo         │",
-            "│             │ bar         │",
-            "╘═════════════╧═════════════╛",
+    fn fancy_sorted_pairs() {
+        //Output: fancy key-value table of a hash map, sorted by key
+        let mut config = HashMap::new();
+        config.insert("timeout", 2.5);
+        config.insert("retries", 3.0);
+        config.insert("backoff", 0.25);
+        let result = Table::from_sorted_pairs(Style::Fancy, config, None).tabulate();
+        let expected = [
+            "╒═════════╦══════╕",
+            "│ backoff ║ 0.25 │",
+            "├─────────╫──────┤",
+            "│ retries ║ 3    │",
+            "├─────────╫──────┤",
+            "│ timeout ║ 2.5  │",
+            "╘═════════╩══════╛",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
+    #[cfg(feature = "ansi_term_style")]
     #[test]
-    fn fancy_grid_multiline_with_empty_cells() {
-        //Output: fancy_grid with multiline cells and empty cells with headers
-        let result = multiline_empty_cells(Style::Fancy).tabulate();
-        let expected = vec![
-            "╒═══════╤════════════════╤════════╕",
-            "│   hdr │ data           │ fold   │",
-            "╞═══════╪════════════════╪════════╡",
-            "│     1 │                │        │",
-            "├───────┼────────────────┼────────┤",
-            "│     2 │ very long data │ fold   │",
-            "│       │                │ this   │",
-            "╘═══════╧════════════════╧════════╛",
+    fn presto_pairs_styled_values() {
+        //Output: presto key-value table keeping the order of the pairs and the styled values
+        let status = ansi_term::Color::Green.paint("up").to_string();
+        let pairs = vec![("service", "api".to_string()), ("status", status)];
+        let headers = Some(Headers::from(vec!["key", "value"]));
+        let result = Table::from_pairs(Style::Presto, pairs, headers).tabulate();
+        let expected = [
+            " key     || value",
+            "---------++---------",
+            " service || api",
+            " status  || \u{1b}[32mup\u{1b}[0m",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn fancy_grid_multiline_with_empty_cells_headerless() {
-        //Output: fancy_grid with multiline cells and empty cells without headers
-        let result = multiline_empty_cells_headerless(Style::Fancy).tabulate();
-        let expected = vec![
-            "╒═══╤════════════════╤══════╕",
-            "│ 0 │                │      │",
-            "├───┼────────────────┼──────┤",
-            "│ 1 │                │      │",
-            "├───┼────────────────┼──────┤",
-            "│ 2 │ very long data │ fold │",
-            "│   │                │ this │",
-            "╘═══╧════════════════╧══════╛",
+    fn grid_fixed_width_under_span() {
+        //Output: grid where a spanning header and the title only widen the column not fixed
+        let mut headers = Headers::new();
+        headers.push_span(String::from("a header spanning both columns"), 2);
+        let mut table = Table::new(
+            Style::Grid,
+            vec![vec![Cell::from("truncated text"), Cell::Int(123456)]],
+            Some(headers),
+        );
+        table.set_col_width(0, Width::Exactly(6));
+        table.set_col_overflow(0, Overflow::Truncate);
+        table.set_title("a title wider than the header and the contents");
+        let result = table.tabulate();
+        let expected = [
+            "+- a title wider than the header and the contents -+",
+            "| a header spanning both columns                   |",
+            "+========+=========================================+",
+            "| trunc… |                                  123456 |",
+            "+--------+-----------------------------------------+",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn presto() {
-        //Output: presto with headers
-        let result = table(Style::Presto).tabulate();
-        let expected = vec![
-            " strings   |   numbers",
-            "-----------+-----------",
-            " spam      |   41.9999",
-            " eggs      |  451",
+    fn simple_min_width_to_width() {
+        //Output: simple fitted in 24 columns, without shrinking a column below its min width
+        let mut table = Table::new(
+            Style::Simple,
+            vec![vec![
+                Cell::from("short"),
+                Cell::from("some text to wrap in the column"),
+            ]],
+            Some(Headers::from(vec!["key", "value"])),
+        );
+        table.set_col_width(0, Width::AtLeast(10));
+        let result = table.tabulate_to_width(24);
+        let expected = [
+            "key         value",
+            "----------  ------------",
+            "short       some text to",
+            "            wrap in the",
+            "            column",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn presto_headerless() {
-        //Output: presto without headers
-        let result = headerless(Style::Presto).tabulate();
-        let expected = vec![" spam |  41.9999", " eggs | 451"].join("\n");
+    fn grid_transposed_grouped_headers() {
+        //Output: grid with the two levels of headers transposed into the first two columns
+        let mut table = grouped_headers(Style::Grid);
+        table.transpose();
+        let result = table.tabulate();
+        let expected = [
+            "+------------------------+--------+-------+",
+            "| host                   | web-01 | db-01 |",
+            "+----------------+-------+--------+-------+",
+            "| disk (MB/s)    | read  | 12     | 230   |",
+            "|                +-------+--------+-------+",
+            "|                | write | 3      | 180   |",
+            "+----------------+-------+--------+-------+",
+            "| network (MB/s) | in    | 140    | 8     |",
+            "|                +-------+--------+-------+",
+            "|                | out   | 95     | 11    |",
+            "+----------------+-------+--------+-------+",
+        ]
+        .join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn presto_multiline_headerless() {
-        //Output: presto with multiline cells without headers
-        let result = multiline_headerless(Style::Presto).tabulate();
-        let expected = vec![
-            " foo bar |   hello",
-            "   baz   |",
-            "   bau   |",
-            "         | multiline",
-            "         |   world",
-        ]
-        .join("\n");
-        assert_eq!(expected, result);
+    fn transpose_ragged_rows() {
+        let rows = vec![
+            vec![Cell::from("a"), Cell::from("b"), Cell::from("c")],
+            vec![Cell::from("d")],
+            vec![Cell::from("e"), Cell::from("f").with_colspan(2)],
+        ];
+        let rows = transpose_rows(rows);
+        let texts: Vec<Vec<String>> = rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|c| c.to_unstylable().unwrap().to_string())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            vec![vec!["a", "d", "e"], vec!["b", "", "f"], vec!["c"]],
+            texts
+        );
+        assert_eq!(Some(2), rows[1][2].format().rowspan);
     }
 
     #[test]
-    fn presto_multiline() {
-        //Output: presto with multiline cells with headers
-        let result = multiline(Style::Presto).tabulate();
-        let expected = vec![
-            "        more | more spam",
-            "   spam eggs | & eggs",
-            "-------------+-------------",
-            "           2 | foo",
-            "             | bar",
+    fn fancy_grid_split_grouped_headers() {
+        //Output: fancy grid split into blocks repeating the index and the first column
+        let mut table = grouped_headers(Style::Fancy);
+        table.set_index(Index::FromOne);
+        table.set_index_rule(true);
+        table.set_key_cols(1);
+        table.set_fit(Fit::Split);
+        let result = table.tabulate_to_width(40);
+        let expected = [
+            "╒════╦════════╤══════════════════╕",
+            "│    ║  host  │   disk (MB/s)    │",
+            "│    ║        ├────────┬─────────┤",
+            "│    ║        │   read │   write │",
+            "╞════╬════════╪════════╪═════════╡",
+            "│  1 ║ web-01 │     12 │       3 │",
+            "├────╫────────┼────────┼─────────┤",
+            "│  2 ║ db-01  │    230 │     180 │",
+            "╘════╩════════╧════════╧═════════╛",
+            "",
+            "╒════╦════════╤══════════════════╕",
+            "│    ║  host  │  network (MB/s)  │",
+            "│    ║        ├────────┬─────────┤",
+            "│    ║        │     in │     out │",
+            "╞════╬════════╪════════╪═════════╡",
+            "│  1 ║ web-01 │    140 │      95 │",
+            "├────╫────────┼────────┼─────────┤",
+            "│  2 ║ db-01  │      8 │      11 │",
+            "╘════╩════════╧════════╧═════════╛",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn presto_multiline_with_empty_cells() {
-        //Output: presto with multiline cells and empty cells with headers
-        let result = multiline_empty_cells(Style::Presto).tabulate();
-        let expected = vec![
-            "   hdr | data           | fold",
-            "-------+----------------+--------",
-            "     1 |                |",
-            "     2 | very long data | fold",
-            "       |                | this",
+    fn split_cols_in_blocks() {
+        let width = |cols: &[usize]| cols.len() * 10;
+        let cols: Vec<usize> = (0..5).collect();
+        assert_eq!(
+            vec![vec![0, 1, 2], vec![0, 3, 4]],
+            split_cols(&cols, 1, 30, width)
+        );
+        assert_eq!(
+            vec![vec![0, 1], vec![0, 2], vec![0, 3], vec![0, 4]],
+            split_cols(&cols, 1, 15, width)
+        );
+        assert_eq!(vec![vec![0, 1, 2, 3, 4]], split_cols(&cols, 0, 50, width));
+        assert_eq!(vec![vec![0]], split_cols(&cols[..1], 2, 5, width));
+    }
+
+    #[test]
+    fn grid_header_repeat_rowspan() {
+        //Output: grid with the header repeated every 2 rows, but not under cells spanning rows
+        let mut table = rowspan(Style::Grid);
+        table.set_header_repeat(PageSize::Rows(2));
+        let result = table.tabulate();
+        let expected = [
+            "+-----------+-----------+--------+",
+            "| host      | service   |   port |",
+            "+===========+===========+========+",
+            "| web-01    | nginx     |    443 |",
+            "|           +-----------+--------+",
+            "|           | sshd      |     22 |",
+            "|           +-----------+--------+",
+            "|           | node      |   9100 |",
+            "|           | exporter  |        |",
+            "+-----------+-----------+--------+",
+            "| host      | service   |   port |",
+            "+===========+===========+========+",
+            "| db-01     | postgres  |   5432 |",
+            "|           +-----------+--------+",
+            "| (replica) | pgbouncer |   6432 |",
+            "+-----------+-----------+--------+",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn presto_multiline_with_empty_cells_headerless() {
-        //Output: presto with multiline cells and empty cells without headers
-        let result = multiline_empty_cells_headerless(Style::Presto).tabulate();
-        let expected = vec![
-            " 0 |                |",
-            " 1 |                |",
-            " 2 | very long data | fold",
-            "   |                | this",
+    fn simple_header_repeat() {
+        //Output: simple with the header repeated every 2 rows
+        let mut table = table(Style::Simple);
+        table.push_row(vec![Cell::from("ham"), Cell::Int(7)]);
+        table.set_header_repeat(PageSize::Rows(2));
+        let result = table.tabulate();
+        let expected = [
+            "strings      numbers",
+            "---------  ---------",
+            "spam         41.9999",
+            "eggs        451",
+            "strings      numbers",
+            "---------  ---------",
+            "ham           7",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn fancygithub_grid() {
-        let result = table(Style::FancyGithub).tabulate();
-        let expected = vec![
+    fn fancy_grid_pages() {
+        //Output: fancy grid in pages of 10 lines, with a title and a footer
+        let mut table = footers(Style::Fancy);
+        table.push_row(vec![Cell::from("ham"), Cell::Int(7)]);
+        table.set_title("menu");
+        let result = table.paginate(PageSize::Lines(10)).join("\n\n");
+        let expected = [
+            "╒═ menu ════╤═══════════╕",
             "│ strings   │   numbers │",
-            "├───────────┼───────────┤",
+            "╞═══════════╪═══════════╡",
             "│ spam      │   41.9999 │",
+            "├───────────┼───────────┤",
             "│ eggs      │  451      │",
+            "╘═══════════╧═══════════╛",
+            "page 1/2",
+            "",
+            "╒═══════════╤═══════════╕",
+            "│ strings   │   numbers │",
+            "╞═══════════╪═══════════╡",
+            "│ ham       │    7      │",
+            "╞═══════════╪═══════════╡",
+            "│ total     │  492.9999 │",
+            "╘═══════════╧═══════════╛",
+            "page 2/2",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn fancygithub_grid_headerless() {
-        let result = headerless(Style::FancyGithub).tabulate();
-        let expected = vec!["│ spam │  41.9999 │", "│ eggs │ 451      │"].join("\n");
-        assert_eq!(expected, result);
+    fn pages_filled_up_to_lines() {
+        //Output: grid and simple pages holding as many rows as their number of lines allows
+        let mut table = table(Style::Grid);
+        table.push_row(vec![Cell::from("ham"), Cell::Int(7)]);
+        let pages = table.paginate(PageSize::Lines(8));
+        let expected = [
+            "+-----------+-----------+",
+            "| strings   |   numbers |",
+            "+===========+===========+",
+            "| spam      |   41.9999 |",
+            "+-----------+-----------+",
+            "| eggs      |  451      |",
+            "+-----------+-----------+",
+            "page 1/2",
+            "",
+            "+-----------+-----------+",
+            "| strings   |   numbers |",
+            "+===========+===========+",
+            "| ham       |    7      |",
+            "+-----------+-----------+",
+            "page 2/2",
+        ]
+        .join("\n");
+        assert_eq!(expected, pages.join("\n\n"));
+        assert!(pages.iter().all(|page| page.lines().count() <= 8));
+        table.style = Style::Simple;
+        let pages = table.paginate(PageSize::Lines(5));
+        assert_eq!(2, pages.len());
+        assert!(pages.iter().all(|page| page.lines().count() <= 5));
     }
 
     #[test]
-    fn fancygithub_grid_multiline_headerless() {
-        let result = multiline_headerless(Style::FancyGithub).tabulate();
-        let expected = vec![
-            "│ foo bar │   hello   │",
-            "│   baz   │           │",
-            "│   bau   │           │",
-            "│         │ multiline │",
-            "│         │   world   │",
+    fn group_rows_sizes() {
+        let breakable = [true, true, false, true, true];
+        assert_eq!(
+            vec![0..1, 1..3, 3..5],
+            group_rows(PageSize::Rows(2), &[1; 5], &[1; 5], &breakable, 0, 0)
+        );
+        assert_eq!(
+            vec![0..1, 1..3, 3..4, 4..5],
+            group_rows(
+                PageSize::Lines(6),
+                &[2, 1, 1, 3, 1],
+                &[1; 5],
+                &breakable,
+                3,
+                0
+            )
+        );
+        assert_eq!(
+            vec![0..2, 2..4, 4..5],
+            group_rows(PageSize::Lines(4), &[1; 5], &[1; 5], &[true; 5], 1, 0)
+        );
+        assert_eq!(
+            vec![0..1, 1..3, 3..4, 4..5],
+            group_rows(PageSize::Lines(0), &[1; 5], &[0; 5], &breakable, 0, 0)
+        );
+        assert_eq!(
+            vec![0..2, 2..3],
+            group_rows(PageSize::Lines(5), &[1; 3], &[1; 3], &[true; 3], 1, 1)
+        );
+        assert!(group_rows(PageSize::Rows(2), &[], &[], &[], 0, 0).is_empty());
+    }
+
+    #[test]
+    fn grid_nested_table_not_cut() {
+        //Output: grid with a nested table wider than the max width of the columns
+        let inner = Table::new(
+            Style::Grid,
+            vec![vec![Cell::from("enabled"), Cell::from("true")]],
+            None,
+        );
+        let mut table = Table::new(
+            Style::Grid,
+            vec![
+                vec![Cell::from("feature flags"), Cell::Table(Box::new(inner))],
+                vec![Cell::from("a"), Cell::from("a text wider than the table")],
+            ],
+            None,
+        );
+        table.set_max_width(10);
+        let result = table.tabulate();
+        let expected = [
+            "+------------+--------------------+",
+            "| feature    | +---------+------+ |",
+            "| flags      | | enabled | true | |",
+            "|            | +---------+------+ |",
+            "+------------+--------------------+",
+            "| a          | a text wider than  |",
+            "|            | the table          |",
+            "+------------+--------------------+",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn fancygithub_grid_multiline() {
-        let result = multiline(Style::FancyGithub).tabulate();
-        let expected = vec![
-            "│        more │ more spam   │",
-            "│   spam eggs │ & eggs      │",
-            "├─────────────┼─────────────┤",
-            "│           2 │ foo         │",
-            "│             │ bar         │",
+    fn grid_nested_borderless_simple() {
+        //Output: grid with a borderless simple table filling a cell spanning two columns
+        let mut inner = Table::new(
+            Style::Simple,
+            vec![
+                vec![Cell::from("old"), Cell::Int(3)],
+                vec![Cell::from("new"), Cell::Int(4)],
+            ],
+            Some(Headers::from(vec!["version", "replicas"])),
+        );
+        inner.set_borderless(true);
+        let table = Table::new(
+            Style::Grid,
+            vec![
+                vec![
+                    Cell::from("diff"),
+                    Cell::Table(Box::new(inner)).with_colspan(2),
+                ],
+                vec![
+                    Cell::from("service"),
+                    Cell::from("a long service name"),
+                    Cell::from("api"),
+                ],
+            ],
+            Some(Headers::from(vec!["key", "value", "other"])),
+        );
+        let result = table.tabulate();
+        let expected = [
+            "+---------+---------------------+---------+",
+            "| key     | value               | other   |",
+            "+=========+=====================+=========+",
+            "| diff    | version              replicas |",
+            "|         | -------------  -------------- |",
+            "|         | old                         3 |",
+            "|         | new                         4 |",
+            "+---------+---------------------+---------+",
+            "| service | a long service name | api     |",
+            "+---------+---------------------+---------+",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn fancygithub_grid_multiline_with_empty_cells() {
-        let result = multiline_empty_cells(Style::FancyGithub).tabulate();
-        let expected = vec![
-            "│   hdr │ data           │ fold   │",
-            "├───────┼────────────────┼────────┤",
-            "│     1 │                │        │",
-            "│     2 │ very long data │ fold   │",
-            "│       │                │ this   │",
+    fn grid_expanded_grouped_headers() {
+        //Output: grid records labelled with the headers of both levels
+        let mut table = grouped_headers(Style::Grid);
+        table.set_expanded(true);
+        let result = table.tabulate();
+        let expected = [
+            "+-[ RECORD 1 ]-------+--------+",
+            "| host               | web-01 |",
+            "| disk (MB/s) read   | 12     |",
+            "| disk (MB/s) write  | 3      |",
+            "| network (MB/s) in  | 140    |",
+            "| network (MB/s) out | 95     |",
+            "+-[ RECORD 2 ]-------+--------+",
+            "| host               | db-01  |",
+            "| disk (MB/s) read   | 230    |",
+            "| disk (MB/s) write  | 180    |",
+            "| network (MB/s) in  | 8      |",
+            "| network (MB/s) out | 11     |",
+            "+--------------------+--------+",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn fancygithub_grid_multiline_with_empty_cells_headerless() {
-        let result = multiline_empty_cells_headerless(Style::FancyGithub).tabulate();
-        let expected = vec![
-            "│ 0 │                │      │",
-            "│ 1 │                │      │",
-            "│ 2 │ very long data │ fold │",
-            "│   │                │ this │",
+    fn simple_expanded_multiline_narrow() {
+        //Output: simple records with multiline values, wrapped to fit 18 columns
+        let mut table = multiline(Style::Simple);
+        table.push_row(vec![Cell::Int(3), Cell::from("a rather long value")]);
+        table.set_expanded(true);
+        let result = table.tabulate_to_width(18);
+        let expected = [
+            "-[ RECORD 1 ]-----",
+            "more       2",
+            "spam eggs",
+            "more spam  foo",
+            "& eggs     bar",
+            "-[ RECORD 2 ]-----",
+            "more       3",
+            "spam eggs",
+            "more spam  a",
+            "& eggs     rather",
+            "           long",
+            "           value",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn fancypresto_grid() {
-        let result = table(Style::FancyPresto).tabulate();
-        let expected = vec![
-            "strings   │   numbers",
-            "──────────┼──────────",
-            "spam      │   41.9999",
-            "eggs      │  451",
+    fn simple_expanded_no_headers() {
+        //Output: simple records of values only, ended by a rule
+        let mut table = table(Style::Simple);
+        table.headers = None;
+        table.set_expanded(true);
+        let result = table.tabulate();
+        let expected = [
+            "-[ RECORD 1 ]-",
+            "spam",
+            "41.9999",
+            "-[ RECORD 2 ]-",
+            "eggs",
+            "451",
+            "--------------",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn fancypresto_grid_headerless() {
-        let result = headerless(Style::FancyPresto).tabulate();
-        let expected = vec!["spam │  41.9999", "eggs │ 451"].join("\n");
+    fn expanded_headers_wider_than_rows() {
+        //Output: records with the headers alone, or with an empty value
+        let headers = || Some(Headers::from(vec!["item", "qty", "extra"]));
+        let mut table = Table::new(Style::Fancy, vec![], headers());
+        table.set_expanded(true);
+        assert_eq!("", table.tabulate());
+        let mut table = Table::new(
+            Style::Grid,
+            vec![vec![Cell::from("spam"), Cell::Int(42)]],
+            headers(),
+        );
+        table.set_expanded(true);
+        let result = table.tabulate();
+        let expected = [
+            "+-[ RECORD 1 ]--+",
+            "| item  | spam  |",
+            "| qty   | 42    |",
+            "| extra |       |",
+            "+-------+-------+",
+        ]
+        .join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn fancypresto_grid_multiline_headerless() {
-        let result = multiline_headerless(Style::FancyPresto).tabulate();
-        let expected = vec![
-            "foo bar │   hello",
-            "  baz   │",
-            "  bau   │",
-            "        │ multiline",
-            "        │   world",
+    fn grid_expanded_uncut_values() {
+        //Output: the values are not shrunk below the width of the numbers
+        let mut table = table(Style::Grid);
+        table.set_expanded(true);
+        let result = table.tabulate_to_width(1);
+        let expected = [
+            "+-[ RECORD 1 ]------+",
+            "| strings | spam    |",
+            "| numbers | 41.9999 |",
+            "+-[ RECORD 2 ]------+",
+            "| strings | eggs    |",
+            "| numbers | 451     |",
+            "+---------+---------+",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
+    #[cfg(feature = "ansi_term_style")]
     #[test]
-    fn fancypresto_grid_multiline() {
-        let result = multiline(Style::FancyPresto).tabulate();
-        let expected = vec![
-            "       more │ more spam",
-            "  spam eggs │ & eggs",
-            "────────────┼────────────",
-            "          2 │ foo",
-            "            │ bar",
+    fn fancy_expanded_styled_borders() {
+        //Output: fancy records with styled borders and record lines
+        let mut table = table(Style::Fancy);
+        table.set_border_style(ansi_term::Color::Green.bold());
+        table.set_expanded(true);
+        let result = table.tabulate();
+        let expected = [
+            "\u{1b}[1;32m╒═\u{1b}[0m[ RECORD 1 ]\u{1b}[1;32m\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═╕\u{1b}[0m",
+            "\u{1b}[1;32m│ \u{1b}[0mstrings\u{1b}[1;32m │ \u{1b}[0mspam   \u{1b}[1;32m │\u{1b}[0m",
+            "\u{1b}[1;32m│ \u{1b}[0mnumbers\u{1b}[1;32m │ \u{1b}[0m41.9999\u{1b}[1;32m │\u{1b}[0m",
+            "\u{1b}[1;32m├─\u{1b}[0m[ RECORD 2 ]\u{1b}[1;32m\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─┤\u{1b}[0m",
+            "\u{1b}[1;32m│ \u{1b}[0mstrings\u{1b}[1;32m │ \u{1b}[0meggs   \u{1b}[1;32m │\u{1b}[0m",
+            "\u{1b}[1;32m│ \u{1b}[0mnumbers\u{1b}[1;32m │ \u{1b}[0m451    \u{1b}[1;32m │\u{1b}[0m",
+            "\u{1b}[1;32m╘═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═╧═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═╛\u{1b}[0m",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
+    fn tree(style: Style) -> Table<'static> {
+        let mut table = Table::new(
+            style,
+            vec![
+                vec![Cell::from("stybulate"), Cell::from("1.1.2")],
+                vec![Cell::from("ansi_term"), Cell::from("0.12.1")],
+                vec![Cell::from("winapi"), Cell::from("0.3.9")],
+                vec![Cell::from("unicode-width"), Cell::from("0.1.9")],
+                vec![
+                    Cell::from("structopt (optional command line)"),
+                    Cell::from("0.3"),
+                ],
+                vec![Cell::from("clap"), Cell::from("2.33")],
+                vec![Cell::from("lazy_static"), Cell::from("1.4.0")],
+            ],
+            Some(Headers::from(vec!["crate", "version"])),
+        );
+        table.set_depths(vec![0, 1, 2, 1, 1, 2, 2]);
+        table
+    }
+
     #[test]
-    fn fancypresto_grid_multiline_with_empty_cells() {
-        let result = multiline_empty_cells(Style::FancyPresto).tabulate();
-        let expected = vec![
-            "  hdr │ data           │ fold",
-            "──────┼────────────────┼───────",
-            "    1 │                │",
-            "    2 │ very long data │ fold",
-            "      │                │ this",
+    fn grid_tree_wrapped() {
+        //Output: grid tree whose first column is wrapped after the guides
+        let result = tree(Style::Grid).tabulate_to_width(30);
+        let expected = [
+            "+----------------+-----------+",
+            "| crate          | version   |",
+            "+================+===========+",
+            "| stybulate      | 1.1.2     |",
+            "+----------------+-----------+",
+            "| ├─ ansi_term   | 0.12.1    |",
+            "+----------------+-----------+",
+            "| │  └─ winapi   | 0.3.9     |",
+            "+----------------+-----------+",
+            "| ├─ unicode-wid | 0.1.9     |",
+            "| │  th          |           |",
+            "+----------------+-----------+",
+            "| └─ structopt   | 0.3       |",
+            "|    (optional   |           |",
+            "|    command     |           |",
+            "|    line)       |           |",
+            "+----------------+-----------+",
+            "|    ├─ clap     | 2.33      |",
+            "+----------------+-----------+",
+            "|    └─ lazy_sta | 1.4.0     |",
+            "|       tic      |           |",
+            "+----------------+-----------+",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
     #[test]
-    fn fancypresto_grid_multiline_with_empty_cells_headerless() {
-        let result = multiline_empty_cells_headerless(Style::FancyPresto).tabulate();
-        let expected = vec![
-            "0 │                │",
-            "1 │                │",
-            "2 │ very long data │ fold",
-            "  │                │ this",
+    fn grid_tree_narrowest() {
+        //Output: grid trees keeping one character after the deepest guides
+        let mut table = Table::new(
+            Style::Grid,
+            vec![
+                vec![Cell::from("spam")],
+                vec![Cell::from("eggs")],
+                vec![Cell::from("ham")],
+            ],
+            None,
+        );
+        table.set_depths(vec![0, 1, 2]);
+        let result = table.tabulate_to_width(1);
+        let expected = [
+            "+---------+",
+            "| spam    |",
+            "+---------+",
+            "| └─ eggs |",
+            "+---------+",
+            "|    └─ h |",
+            "|       a |",
+            "|       m |",
+            "+---------+",
         ]
         .join("\n");
         assert_eq!(expected, result);
+        table.set_max_width(2);
+        assert_eq!(result, table.tabulate());
     }
 
-    #[cfg(feature = "ansi_term_style")]
     #[test]
-    fn ansi_term_colored_content<'a>() {
-        use ansi_term::Colour::Red;
-        use ansi_term::{ANSIString, ANSIStrings};
+    fn simple_tree_index() {
+        //Output: simple tree after an index column
+        let mut table = tree(Style::Simple);
+        table.set_index(Index::FromOne);
+        let result = table.tabulate();
+        let expected = [
+            "    crate                                 version",
+            "--  ------------------------------------  ---------",
+            " 1  stybulate                             1.1.2",
+            " 2  ├─ ansi_term                          0.12.1",
+            " 3  │  └─ winapi                          0.3.9",
+            " 4  ├─ unicode-width                      0.1.9",
+            " 5  └─ structopt (optional command line)  0.3",
+            " 6     ├─ clap                            2.33",
+            " 7     └─ lazy_static                     1.4.0",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
 
-        let some_value = format!("{:b}", 42);
-        let strings: &[ANSIString<'a>] =
-            &[Red.paint("["), Red.bold().paint(some_value), Red.paint("]")];
+    #[test]
+    fn guides_of_depths() {
+        let guides = get_guides(&[1, 2, 1, 3, 0], 6);
+        let firsts: Vec<&str> = guides
+            .iter()
+            .map(|guides| guides.as_ref().map_or("", |(first, _)| first.as_str()))
+            .collect();
+        assert_eq!(vec!["├─ ", "│  └─ ", "└─ ", "      └─ ", "", ""], firsts);
+        let others = guides[0].as_ref().map(|(_, others)| others.as_str());
+        assert_eq!(Some("│  "), others);
+    }
 
-        let result = Table::new(
-            Style::Grid,
-            vec![vec![
-                Cell::Int(42),
-                Cell::Text(Box::new(ANSIStrings(&strings))),
-            ]],
-            Some(Headers::from(vec!["Int", "Colored binary"])),
-        )
-        .tabulate();
+    #[test]
+    fn simple_indented_padded_lines() {
+        //Output: simple indented by 4 spaces, with lines padded to the width of the table
+        let mut table = multiline_headerless(Style::Simple);
+        table.set_margin(Margin::Left(4));
+        table.set_pad_lines(true);
+        let result = table.tabulate();
+        let expected = [
+            "    -------  ---------",
+            "    foo bar    hello  ",
+            "      baz             ",
+            "      bau             ",
+            "             multiline",
+            "               world  ",
+            "    -------  ---------",
+        ]
+        .join("\n");
+        assert_eq!(expected, result);
+    }
 
-        let expected = vec![
-            "+-------+------------------+",
-            "|   Int | Colored binary   |",
-            "+=======+==================+",
-            "|    42 | \u{1b}[31m[\u{1b}[1m101010\u{1b}[0m\u{1b}[31m]\u{1b}[0m         |",
-            "+-------+------------------+",
+    #[test]
+    fn grid_indented_to_width() {
+        //Output: grid with a left margin taken from the width it is fitted in
+        let mut table = multiline(Style::Grid);
+        table.set_margin(Margin::Left(2));
+        let result = table.tabulate_to_width(20);
+        let expected = [
+            "  +-------+--------+",
+            "  |  more | more   |",
+            "  |  spam | spam   |",
+            "  |  eggs | & eggs |",
+            "  +=======+========+",
+            "  |     2 | foo    |",
+            "  |       | bar    |",
+            "  +-------+--------+",
         ]
         .join("\n");
         assert_eq!(expected, result);
     }
 
-    #[cfg(feature = "ansi_term_style")]
     #[test]
-    fn ansi_styled_borders() {
-        let mut table = table(Style::Fancy);
-        table.set_border_style(ansi_term::Color::Green.bold());
+    fn presto_expanded_centered() {
+        //Output: presto records centered in 30 columns (and not indented in 10 columns)
+        let mut table = table(Style::Presto);
+        table.set_expanded(true);
+        let flush = table.tabulate();
+        table.set_margin(Margin::Center(30));
         let result = table.tabulate();
-        let expected = vec![
-            "\u{1b}[1;32m╒═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═╤═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═╕\u{1b}[0m",
-            "\u{1b}[1;32m│ \u{1b}[0mstrings  \u{1b}[1;32m │ \u{1b}[0m  numbers\u{1b}[1;32m │\u{1b}[0m",
-            "\u{1b}[1;32m╞═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═╪═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═╡\u{1b}[0m",
-            "\u{1b}[1;32m│ \u{1b}[0mspam     \u{1b}[1;32m │ \u{1b}[0m  41.9999\u{1b}[1;32m │\u{1b}[0m",
-            "\u{1b}[1;32m├─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─┼─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─\u{1b}[0m\u{1b}[1;32m─┤\u{1b}[0m",
-            "\u{1b}[1;32m│ \u{1b}[0meggs     \u{1b}[1;32m │ \u{1b}[0m 451     \u{1b}[1;32m │\u{1b}[0m",
-            "\u{1b}[1;32m╘═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═╧═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═\u{1b}[0m\u{1b}[1;32m═╛\u{1b}[0m"
-        ].join("\n");
+        let expected = [
+            "     -[ RECORD 1 ]------",
+            "      strings | spam",
+            "      numbers | 41.9999",
+            "     -[ RECORD 2 ]------",
+            "      strings | eggs",
+            "      numbers | 451",
+        ]
+        .join("\n");
         assert_eq!(expected, result);
+        table.set_margin(Margin::Center(10));
+        assert_eq!(flush, table.tabulate());
     }
 }
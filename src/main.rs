@@ -28,6 +28,39 @@ struct Opt {
     /// Defaults to simple.
     #[structopt(short, long, default_value = "simple")]
     fmt: String,
+
+    /// Fit the table in this number of columns, or in the terminal width with "auto".
+    /// The widest text columns are wrapped (or truncated with --truncate).
+    #[structopt(short, long)]
+    width: Option<String>,
+
+    /// Truncate texts instead of wrapping them when fitting the table in --width
+    #[structopt(long)]
+    truncate: bool,
+
+    /// Repeat the header every this number of rows, or every terminal page with "auto"
+    #[structopt(long)]
+    repeat_header: Option<String>,
+
+    /// Prepend an index column numbering the rows from 0
+    #[structopt(long, conflicts_with = "expanded")]
+    index: bool,
+
+    /// Transpose the table: the header becomes the first column and each row a column
+    #[structopt(long)]
+    transpose: bool,
+
+    /// Print each row as a record of "header | value" lines
+    #[structopt(long)]
+    expanded: bool,
+
+    /// Indent the table by this number of spaces
+    #[structopt(long)]
+    indent: Option<usize>,
+
+    /// Center the table in --width, or in the terminal width
+    #[structopt(long, conflicts_with = "indent")]
+    center: bool,
 }
 
 fn main() -> Result<()> {
@@ -37,6 +70,34 @@ fn main() -> Result<()> {
     // Style
     let fmt = Style::from(&opt.fmt).ok_or(anyhow!("Unsupported format \"{}\"", opt.fmt))?;
 
+    // Width
+    let terminal_width = || match terminal_size::terminal_size() {
+        Some((terminal_size::Width(w), _)) => w as usize,
+        None => 80,
+    };
+    let width = match opt.width.as_deref() {
+        None => None,
+        Some("auto") => Some(terminal_width()),
+        Some(w) => Some(
+            w.parse::<usize>()
+                .with_context(|| format!("Invalid width \"{}\"", w))?,
+        ),
+    };
+
+    // Header repeat
+    let repeat = match opt.repeat_header.as_deref() {
+        None => None,
+        Some("auto") => Some(PageSize::Lines(match terminal_size::terminal_size() {
+            Some((_, terminal_size::Height(h))) => h as usize,
+            None => 24,
+        })),
+        Some(n) => {
+            Some(PageSize::Rows(n.parse::<usize>().with_context(|| {
+                format!("Invalid number of rows \"{}\"", n)
+            })?))
+        }
+    };
+
     // Output
     let mut writer: Box<dyn Write> = match opt.output {
         None => Box::new(BufWriter::new(io::stdout())),
@@ -83,7 +144,33 @@ fn main() -> Result<()> {
     }
 
     // Tabulate
-    writeln!(writer, "{}", Table::new(fmt, contents, headers).tabulate())?;
+    let mut table = Table::new(fmt, contents, headers);
+    if opt.truncate {
+        table.set_overflow(Overflow::Truncate);
+    }
+    if opt.transpose {
+        table.transpose();
+    }
+    if opt.index {
+        table.set_index(Index::FromZero);
+    }
+    if opt.expanded {
+        table.set_expanded(true);
+    }
+    if let Some(indent) = opt.indent {
+        table.set_margin(Margin::Left(indent));
+    }
+    if opt.center {
+        table.set_margin(Margin::Center(width.unwrap_or_else(terminal_width)));
+    }
+    if let Some(size) = repeat {
+        table.set_header_repeat(size);
+    }
+    let output = match width {
+        Some(width) => table.tabulate_to_width(width),
+        None => table.tabulate(),
+    };
+    writeln!(writer, "{}", output)?;
 
     Ok(())
 }
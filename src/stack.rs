@@ -0,0 +1,94 @@
+use crate::wrap;
+
+/// Puts blocks of lines (such as tabulated tables) side by side, separated by `gutter` spaces
+///
+/// The lines of each block are padded to the width of its widest one, escape sequences
+/// excluded, and the shorter blocks are padded with blank lines at their bottom.
+/// # Example
+/// ```
+/// use stybulate::{hstack, Table, Style, Cell, Headers};
+/// let left = Table::new(
+///     Style::Grid,
+///     vec![vec![Cell::from("spam"), Cell::Int(42)]],
+///     Some(Headers::from(vec!["item", "qty"])),
+/// );
+/// let expected = [
+///     "+--------+-------+  a note",
+///     "| item   |   qty |  on two lines",
+///     "+========+=======+",
+///     "| spam   |    42 |",
+///     "+--------+-------+",
+/// ].join("\n");
+/// assert_eq!(expected, hstack(&[left.tabulate().as_str(), "a note\non two lines"], 2));
+/// ```
+pub fn hstack<S: AsRef<str>>(blocks: &[S], gutter: usize) -> String {
+    let blocks: Vec<Vec<&str>> = blocks
+        .iter()
+        .map(|block| block.as_ref().split('\n').collect())
+        .collect();
+    let widths: Vec<usize> = blocks
+        .iter()
+        .map(|lines| {
+            lines
+                .iter()
+                .map(|line| wrap::width(line))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let height = blocks.iter().map(Vec::len).max().unwrap_or(0);
+    let gutter = " ".repeat(gutter);
+    let lines: Vec<String> = (0..height)
+        .map(|i| {
+            let parts: Vec<&str> = blocks
+                .iter()
+                .map(|lines| lines.get(i).copied().unwrap_or(""))
+                .collect();
+            // no padding after the last part
+            let last = parts.iter().rposition(|part| !part.is_empty()).unwrap_or(0);
+            let mut line = String::new();
+            for (b, (part, width)) in parts.iter().zip(&widths).enumerate().take(last + 1) {
+                if b > 0 {
+                    line.push_str(&gutter);
+                }
+                line.push_str(part);
+                if b < last {
+                    line.push_str(&" ".repeat(width - wrap::width(part)));
+                }
+            }
+            line
+        })
+        .collect();
+    lines.join("\n")
+}
+
+/// Puts blocks of lines (such as tabulated tables) one below the other, separated by `gap`
+/// blank lines
+pub fn vstack<S: AsRef<str>>(blocks: &[S], gap: usize) -> String {
+    let gap = "\n".repeat(gap + 1);
+    let blocks: Vec<&str> = blocks.iter().map(AsRef::as_ref).collect();
+    blocks.join(&gap)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hstack_styled_and_wide_lines() {
+        let left = "\x1b[1mabc\x1b[0m\n漢";
+        let right = "x\ny\nz";
+        assert_eq!(
+            "\x1b[1mabc\x1b[0m  x\n漢   y\n     z",
+            hstack(&[left, right], 2)
+        );
+        assert_eq!("a b  \n  c", hstack(&["a", "b  \nc"], 1));
+        assert_eq!("", hstack::<&str>(&[], 1));
+    }
+
+    #[test]
+    fn vstack_with_gap() {
+        assert_eq!("a\nb\n\nc", vstack(&["a\nb", "c"], 1));
+        assert_eq!("a\nc", vstack(&[String::from("a"), String::from("c")], 0));
+    }
+}
@@ -1,3 +1,5 @@
+use crate::wrap;
+
 /// The style of the table
 ///
 /// Examples shown will have a header line and two content lines
@@ -84,11 +86,14 @@ impl Style {
 
     /// Returns the corresponding format
     pub fn to_format(&self) -> TableFormat {
-        let basicrow = DataRow::new("", "  ", "");
+        let basicrow = DataRow::new("", "  ", "").with_rule(" | ");
         let emptyformat = TableFormat {
             lineabove: None,
+            linebetweenheaders: None,
             linebelowheader: None,
             linebetweenrows: None,
+            linebetweensections: None,
+            lineabovefooter: None,
             linebelow: None,
             headerrow: basicrow.clone(),
             datarow: basicrow,
@@ -96,23 +101,37 @@ impl Style {
             hidelineaboveifheader: false,
             hidelinebelowifheader: false,
         };
-        let basicline = Line::new("", "-", "  ", "");
+        // under a spanning cell, a simple line is not split
+        let basicline = Line::new("", "-", "  ", "")
+            .with_junctions("--", "  ")
+            .with_rule(Junctions::new("-+-").with_tees(" +-", "-+ "));
         let piperow = DataRow::new("| ", " | ", " |");
-        let single_line = Line::new("", "─", "─┼─", "");
-        let single_line_with_ends = Line::new("├─", "─", "─┼─", "─┤");
-        let row_line = DataRow::new("", " │ ", "");
-        let row_line_with_ends = DataRow::new("│ ", " │ ", " │");
+        let single_rule = Junctions::new("─╫─")
+            .with_junctions("─╥─", "─╨─")
+            .with_tees(" ╟─", "─╢ ");
+        let single_line = Line::new("", "─", "─┼─", "")
+            .with_junctions("─┬─", "─┴─")
+            .with_rule(single_rule.clone());
+        let single_line_with_ends = Line::new("├─", "─", "─┼─", "─┤")
+            .with_junctions("─┬─", "─┴─")
+            .with_rule(single_rule);
+        let row_line = DataRow::new("", " │ ", "").with_rule(" ║ ");
+        let row_line_with_ends = DataRow::new("│ ", " │ ", " │").with_rule(" ║ ");
         match self {
             Self::Plain => emptyformat,
             Self::Simple => TableFormat {
                 lineabove: Some(basicline.clone()),
+                linebetweenheaders: Some(basicline.clone()),
                 linebelowheader: Some(basicline.clone()),
+                linebetweensections: Some(basicline.clone()),
+                lineabovefooter: Some(basicline.clone()),
                 linebelow: Some(basicline),
                 hidelineaboveifheader: true,
                 hidelinebelowifheader: true,
                 ..emptyformat
             },
             Self::Github => {
+                // Markdown has no other line than the header delimiter
                 let line = Line::new("|-", "-", "-|-", "-|");
                 TableFormat {
                     lineabove: Some(line.clone()),
@@ -125,32 +144,53 @@ impl Style {
                 }
             }
             Self::Grid => {
-                let line = Line::new("+-", "-", "-+-", "-+");
+                let rule = Junctions::new("-++-").with_tees(" ++-", "-++ ");
+                let line = Line::new("+-", "-", "-+-", "-+").with_rule(rule);
+                let doubleline =
+                    Line::new("+=", "=", "=+=", "=+").with_rule(Junctions::new("=++="));
+                let row = piperow.with_rule(" || ");
                 TableFormat {
                     lineabove: Some(line.clone()),
-                    linebelowheader: Some(Line::new("+=", "=", "=+=", "=+")),
-                    linebetweenrows: Some(line.clone()),
+                    linebetweenheaders: Some(line.clone().with_tees(" +-", "-+ ")),
+                    linebelowheader: Some(doubleline.clone()),
+                    linebetweenrows: Some(line.clone().with_tees(" +-", "-+ ")),
+                    linebetweensections: Some(line.clone()),
+                    lineabovefooter: Some(doubleline),
                     linebelow: Some(line),
-                    headerrow: piperow.clone(),
-                    datarow: piperow,
+                    headerrow: row.clone(),
+                    datarow: row,
+                    padding: 1,
+                    ..emptyformat
+                }
+            }
+            Self::Fancy => {
+                let doubleline = Line::new("╞═", "═", "═╪═", "═╡")
+                    .with_junctions("═╤═", "═╧═")
+                    .with_rule(Junctions::new("═╬═").with_junctions("═╦═", "═╩═"));
+                TableFormat {
+                    lineabove: Some(
+                        Line::new("╒═", "═", "═╤═", "═╕").with_rule(Junctions::new("═╦═")),
+                    ),
+                    linebetweenheaders: Some(single_line_with_ends.clone().with_tees(" ├─", "─┤ ")),
+                    linebelowheader: Some(doubleline.clone()),
+                    linebetweenrows: Some(single_line_with_ends.clone().with_tees(" ├─", "─┤ ")),
+                    linebetweensections: Some(single_line_with_ends.clone()),
+                    lineabovefooter: Some(doubleline),
+                    linebelow: Some(
+                        Line::new("╘═", "═", "═╧═", "═╛").with_rule(Junctions::new("═╩═")),
+                    ),
+                    headerrow: row_line_with_ends.clone(),
+                    datarow: row_line_with_ends,
                     padding: 1,
                     ..emptyformat
                 }
             }
-            Self::Fancy => TableFormat {
-                lineabove: Some(Line::new("╒═", "═", "═╤═", "═╕")),
-                linebelowheader: Some(Line::new("╞═", "═", "═╪═", "═╡")),
-                linebetweenrows: Some(single_line_with_ends),
-                linebelow: Some(Line::new("╘═", "═", "═╧═", "═╛")),
-                headerrow: row_line_with_ends.clone(),
-                datarow: row_line_with_ends,
-                padding: 1,
-                ..emptyformat
-            },
             Self::Presto => {
-                let row = DataRow::new(" ", " | ", " ");
+                let row = DataRow::new(" ", " | ", " ").with_rule(" || ");
+                let line = Line::new("-", "-", "-+-", "-").with_rule(Junctions::new("-++-"));
                 TableFormat {
-                    linebelowheader: Some(Line::new("-", "-", "-+-", "-")),
+                    linebelowheader: Some(line.clone()),
+                    lineabovefooter: Some(line),
                     headerrow: row.clone(),
                     datarow: row,
                     padding: 1,
@@ -158,14 +198,18 @@ impl Style {
                 }
             }
             Self::FancyGithub => TableFormat {
-                linebelowheader: Some(single_line_with_ends),
+                linebelowheader: Some(single_line_with_ends.clone()),
+                linebetweensections: Some(single_line_with_ends.clone()),
+                lineabovefooter: Some(single_line_with_ends),
                 headerrow: row_line_with_ends.clone(),
                 datarow: row_line_with_ends,
                 padding: 1,
                 ..emptyformat
             },
             Self::FancyPresto => TableFormat {
-                linebelowheader: Some(single_line),
+                linebelowheader: Some(single_line.clone()),
+                linebetweensections: Some(single_line.clone()),
+                lineabovefooter: Some(single_line),
                 headerrow: row_line.clone(),
                 datarow: row_line,
                 padding: 1,
@@ -178,7 +222,7 @@ impl Style {
 /// The column alignments
 ///
 /// Numbers are only considered as non-text when align is `Decimal`.
-#[derive(PartialEq)]
+#[derive(Clone, Copy, PartialEq)]
 pub enum Align {
     /// Left aligned text
     Left,
@@ -190,11 +234,132 @@ pub enum Align {
     Decimal,
 }
 
+/// How texts wider than the maximum width of their column are fitted
+#[derive(Clone, Copy, PartialEq)]
+pub enum Overflow {
+    /// Texts are wrapped at word boundaries into multiple lines
+    Wrap,
+    /// Each line is cut and ends with a marker (`…` by default)
+    Truncate,
+}
+
+/// How a table too wide for [`Table::tabulate_to_width`](struct.Table.html#method.tabulate_to_width)
+/// is fitted
+#[derive(Clone, Copy, PartialEq)]
+pub enum Fit {
+    /// The widest columns are shrunk (their texts are wrapped or truncated)
+    Shrink,
+    /// Whole columns are hidden, lowest priority first, then the remaining ones are shrunk if
+    /// needed (the index, stub and key columns are never hidden)
+    HideColumns,
+    /// The columns are split into blocks rendered one below the other, each of them repeating
+    /// the key columns (see [`Table::set_key_cols`](struct.Table.html#method.set_key_cols)),
+    /// then the blocks still too wide are shrunk
+    Split,
+}
+
+/// Width of a column set with [`Table::set_col_width`](struct.Table.html#method.set_col_width),
+/// padding included
+#[derive(Clone, Copy, PartialEq)]
+pub enum Width {
+    /// The column is at least this wide
+    AtLeast(usize),
+    /// The column is exactly this wide: wider texts are wrapped or truncated depending on its
+    /// [`Overflow`](enum.Overflow.html) policy (numbers are never cut though)
+    Exactly(usize),
+}
+
+/// Size of the groups of rows after which the headers are repeated (see
+/// [`Table::set_header_repeat`](struct.Table.html#method.set_header_repeat)), or of the pages
+/// (see [`Table::paginate`](struct.Table.html#method.paginate))
+#[derive(Clone, Copy, PartialEq)]
+pub enum PageSize {
+    /// Number of content rows
+    Rows(usize),
+    /// Number of lines, the headers and the lines around them included
+    Lines(usize),
+}
+
+/// Horizontal position of the table (see
+/// [`Table::set_margin`](struct.Table.html#method.set_margin))
+#[derive(Clone, Copy, PartialEq)]
+pub enum Margin {
+    /// Number of spaces before each line
+    Left(usize),
+    /// Width in which the table is centered
+    Center(usize),
+}
+
+/// Labels of the index column (see [`Table::set_index`](struct.Table.html#method.set_index))
+#[derive(Clone, PartialEq)]
+pub enum Index {
+    /// Rows numbered from 0
+    FromZero,
+    /// Rows numbered from 1
+    FromOne,
+    /// One label per row, rows without one getting an empty label
+    Labels(Vec<String>),
+}
+
+// The junctions of a line depend on the rows around it (because of the spanning cells):
+//     cross: both rows are split (the default)
+//     down:  only the row below is split
+//     up:    only the row above is split
+// When none of them is split, hline is used.
+// Under a cell spanning the rows around it, the line is not drawn and the junctions next to it
+// become tees:
+//     right: the line only goes on the right
+//     left:  the line only goes on the left
+#[derive(Clone)]
+pub struct Junctions {
+    pub cross: String,
+    pub down: String,
+    pub up: String,
+    pub right: String,
+    pub left: String,
+}
+impl Junctions {
+    fn new(cross: &str) -> Self {
+        Self {
+            cross: String::from(cross),
+            down: String::from(cross),
+            up: String::from(cross),
+            right: String::from(cross),
+            left: String::from(cross),
+        }
+    }
+
+    fn with_junctions(mut self, down: &str, up: &str) -> Self {
+        self.down = String::from(down);
+        self.up = String::from(up);
+        self
+    }
+
+    fn with_tees(mut self, right: &str, left: &str) -> Self {
+        self.right = String::from(right);
+        self.left = String::from(left);
+        self
+    }
+
+    #[cfg(feature = "ansi_term_style")]
+    /// Apply style to junctions
+    pub fn apply_style(&mut self, style: ansi_term::Style) {
+        self.cross = paint(&self.cross, style);
+        self.down = paint(&self.down, style);
+        self.up = paint(&self.up, style);
+        self.right = paint(&self.right, style);
+        self.left = paint(&self.left, style);
+    }
+}
+
+// The junctions with the separators between the columns (sep) or with the rules after some
+// columns (rule, the same as sep by default)
 #[derive(Clone)]
 pub struct Line {
     pub begin: String,
     pub hline: String,
-    pub sep: String,
+    pub sep: Junctions,
+    pub rule: Junctions,
     pub end: String,
 }
 impl Line {
@@ -202,17 +367,34 @@ impl Line {
         Self {
             begin: String::from(begin),
             hline: String::from(hline),
-            sep: String::from(sep),
+            sep: Junctions::new(sep),
+            rule: Junctions::new(sep),
             end: String::from(end),
         }
     }
 
+    fn with_junctions(mut self, down: &str, up: &str) -> Self {
+        self.sep = self.sep.with_junctions(down, up);
+        self
+    }
+
+    fn with_tees(mut self, right: &str, left: &str) -> Self {
+        self.sep = self.sep.with_tees(right, left);
+        self
+    }
+
+    fn with_rule(mut self, rule: Junctions) -> Self {
+        self.rule = rule;
+        self
+    }
+
     #[cfg(feature = "ansi_term_style")]
     /// Apply style to line
     pub fn apply_style(&mut self, style: ansi_term::Style) {
         self.begin = paint(&self.begin, style);
         self.hline = paint(&self.hline, style);
-        self.sep = paint(&self.sep, style);
+        self.sep.apply_style(style);
+        self.rule.apply_style(style);
         self.end = paint(&self.end, style);
     }
 }
@@ -221,6 +403,7 @@ impl Line {
 pub struct DataRow {
     pub begin: String,
     pub sep: String,
+    pub rule: String,
     pub end: String,
 }
 impl DataRow {
@@ -228,15 +411,22 @@ impl DataRow {
         Self {
             begin: String::from(begin),
             sep: String::from(sep),
+            rule: String::from(sep),
             end: String::from(end),
         }
     }
 
+    fn with_rule(mut self, rule: &str) -> Self {
+        self.rule = String::from(rule);
+        self
+    }
+
     #[cfg(feature = "ansi_term_style")]
     /// Apply style to datarow
     pub fn apply_style(&mut self, style: ansi_term::Style) {
         self.begin = paint(&self.begin, style);
         self.sep = paint(&self.sep, style);
+        self.rule = paint(&self.rule, style);
         self.end = paint(&self.end, style);
     }
 }
@@ -247,20 +437,32 @@ fn paint(s: &str, style: ansi_term::Style) -> String {
 }
 
 // A table is structured like so:
-//     --- lineabove ---------
+//     --- lineabove ----------
 //         headerrow
-//     --- linebelowheader ---
+//     --- linebetweenheaders -
+//     ... (more headerrows) ...
+//     --- linebelowheader ----
 //         datarow
 //     --- linebetweenrows ---
 //     ... (more datarows) ...
 //     --- linebewteenrows ---
-//         last datarow
+//         last datarow of a section
+//     - linebetweensections -
+//         section heading
+//     --- linebetweenrows ---
+//     ... (more sections) ...
+//     --- lineabovefooter ---
+//         footerrow
+//     ... (more footerrows, separated by linebetweenrows) ...
 //     --- linebelow ---------
 #[derive(Clone)]
 pub struct TableFormat {
     pub lineabove: Option<Line>,
+    pub linebetweenheaders: Option<Line>,
     pub linebelowheader: Option<Line>,
     pub linebetweenrows: Option<Line>,
+    pub linebetweensections: Option<Line>,
+    pub lineabovefooter: Option<Line>,
     pub linebelow: Option<Line>,
     pub headerrow: DataRow,
     pub datarow: DataRow,
@@ -269,6 +471,43 @@ pub struct TableFormat {
     pub hidelinebelowifheader: bool,
 }
 
+impl TableFormat {
+    /// Removes the outer borders: the lines above and below the table, and the beginning and
+    /// the end of the other lines
+    pub fn remove_borders(&mut self) {
+        self.lineabove = None;
+        self.linebelow = None;
+        let mut lines = [
+            &mut self.linebetweenheaders,
+            &mut self.linebelowheader,
+            &mut self.linebetweenrows,
+            &mut self.linebetweensections,
+            &mut self.lineabovefooter,
+        ];
+        for line in lines.iter_mut().filter_map(|line| line.as_mut()) {
+            line.begin.clear();
+            line.end.clear();
+        }
+        for row in [&mut self.headerrow, &mut self.datarow].iter_mut() {
+            row.begin.clear();
+            row.end.clear();
+        }
+    }
+
+    /// Line above each record in the expanded mode: the line between rows, the one below the
+    /// headers or else a dashed line
+    pub fn recordline(&self) -> Line {
+        let line = self
+            .linebetweenrows
+            .as_ref()
+            .or(self.linebelowheader.as_ref());
+        match line {
+            Some(line) => line.clone(),
+            None => Line::new("", "-", &"-".repeat(wrap::width(&self.datarow.sep)), ""),
+        }
+    }
+}
+
 #[cfg(feature = "ansi_term_style")]
 impl TableFormat {
     /// Apply the style to all the Strings in the TableFormat
@@ -276,12 +515,21 @@ impl TableFormat {
         if let Some(la) = self.lineabove.as_mut() {
             la.apply_style(style);
         }
+        if let Some(lbhs) = self.linebetweenheaders.as_mut() {
+            lbhs.apply_style(style);
+        }
         if let Some(lbh) = self.linebelowheader.as_mut() {
             lbh.apply_style(style);
         }
         if let Some(lbr) = self.linebetweenrows.as_mut() {
             lbr.apply_style(style);
         }
+        if let Some(lbs) = self.linebetweensections.as_mut() {
+            lbs.apply_style(style);
+        }
+        if let Some(laf) = self.lineabovefooter.as_mut() {
+            laf.apply_style(style);
+        }
         if let Some(lb) = self.linebelow.as_mut() {
             lb.apply_style(style);
         }
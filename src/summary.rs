@@ -0,0 +1,142 @@
+use crate::cell::Cell;
+use crate::get_colspan;
+
+/// Aggregate functions of the summary rows (see
+/// [`Table::add_summary`](struct.Table.html#method.add_summary))
+#[derive(Clone, Copy, PartialEq)]
+pub enum Agg {
+    /// Sum of the numbers
+    Sum,
+    /// Arithmetic mean of the numbers
+    Mean,
+    /// Smallest number
+    Min,
+    /// Largest number
+    Max,
+    /// Number of numbers
+    Count,
+}
+
+impl Agg {
+    /// Label of the summary row
+    fn label(self) -> &'static str {
+        match self {
+            Self::Sum => "sum",
+            Self::Mean => "mean",
+            Self::Min => "min",
+            Self::Max => "max",
+            Self::Count => "count",
+        }
+    }
+
+    fn compute(self, values: &[f64]) -> Cell<'static> {
+        match self {
+            Self::Sum => Cell::Float(values.iter().sum()),
+            Self::Mean => Cell::Float(values.iter().sum::<f64>() / values.len() as f64),
+            Self::Min => Cell::Float(values.iter().copied().fold(std::f64::INFINITY, f64::min)),
+            Self::Max => Cell::Float(
+                values
+                    .iter()
+                    .copied()
+                    .fold(std::f64::NEG_INFINITY, f64::max),
+            ),
+            Self::Count => Cell::Int(values.len() as i32),
+        }
+    }
+}
+
+/// Summary rows: one per aggregate, computed over the numbers of each `numeric` column and
+/// labelled in the first other column from `first` (the columns before it, such as the index,
+/// only get the label when all the next ones are numeric)
+///
+/// `rows` are the cells (with their first column) of the rows to summarize.
+pub fn summarize(
+    aggs: &[Agg],
+    numeric: &[bool],
+    first: usize,
+    rows: &[Vec<(usize, &Cell)>],
+) -> Vec<Vec<Cell<'static>>> {
+    let mut values = vec![Vec::new(); numeric.len()];
+    for (col, cell) in rows.iter().flatten() {
+        // spanning cells do not belong to a single column
+        if get_colspan(cell) > 1 {
+            continue;
+        }
+        match cell.content() {
+            Cell::Int(i) => values[*col].push(f64::from(*i)),
+            Cell::Float(f) => values[*col].push(*f),
+            _ => (),
+        }
+    }
+    let label_col = (first..numeric.len())
+        .chain(0..first)
+        .find(|col| !numeric[*col]);
+    aggs.iter()
+        .map(|agg| {
+            (0..numeric.len())
+                .map(|col| {
+                    if Some(col) == label_col {
+                        Cell::from(agg.label())
+                    } else if numeric[col] && !values[col].is_empty() {
+                        agg.compute(&values[col])
+                    } else {
+                        Cell::from("")
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summarize_columns() {
+        let cells = [
+            Cell::from("a"),
+            Cell::Int(1),
+            Cell::Float(2.5),
+            Cell::Int(-3),
+        ];
+        let rows = vec![
+            vec![(0, &cells[0]), (1, &cells[1])],
+            vec![(0, &cells[0]), (1, &cells[2])],
+            vec![(0, &cells[0]), (1, &cells[3])],
+        ];
+        let summary = summarize(&[Agg::Sum, Agg::Min, Agg::Count], &[false, true], 0, &rows);
+        let texts: Vec<Vec<String>> = summary
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| match cell.to_string() {
+                        Some(s) => s,
+                        None => cell.to_unstylable().unwrap().to_string(),
+                    })
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            vec![vec!["sum", "0.5"], vec!["min", "-3"], vec!["count", "3"]],
+            texts
+        );
+    }
+    #[test]
+    fn summarize_label_after_index() {
+        let cells = [Cell::Int(0), Cell::from("a"), Cell::Int(1)];
+        let rows = vec![vec![(0, &cells[0]), (1, &cells[1]), (2, &cells[2])]];
+        let texts = |numeric: &[bool]| -> Vec<String> {
+            summarize(&[Agg::Sum], numeric, 1, &rows)[0]
+                .iter()
+                .map(|cell| match cell.to_string() {
+                    Some(s) => s,
+                    None => cell.to_unstylable().unwrap().to_string(),
+                })
+                .collect()
+        };
+        assert_eq!(vec!["", "sum", "1"], texts(&[false, false, true]));
+        // the index only gets the label when all the other columns are numeric
+        assert_eq!(vec!["sum", "", "1"], texts(&[false, true, true]));
+    }
+}
@@ -86,6 +86,6 @@ mod tests {
         let strings: &[ANSIString<'static>] =
             &[Red.paint("["), Red.bold().paint(some_value), Red.paint("]")];
 
-        assert_eq!("[101010]", ANSIStrings(&strings).unstyle());
+        assert_eq!("[101010]", ANSIStrings(strings).unstyle());
     }
 }
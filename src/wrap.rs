@@ -0,0 +1,297 @@
+use std::cmp;
+use std::mem;
+
+use unicode_width::UnicodeWidthChar;
+
+const RESET: &str = "\x1b[0m";
+
+/// Piece of a string which may contain ANSI escape sequences
+#[derive(Clone, Copy)]
+enum Token<'a> {
+    /// Escape sequence (zero width)
+    Escape(&'a str),
+    /// Visible char
+    Char(char),
+}
+
+fn tokenize(s: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut chars = s.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        if c != '\x1b' {
+            tokens.push(Token::Char(c));
+            continue;
+        }
+        let mut end = start + c.len_utf8();
+        if let Some(&(_, '[')) = chars.peek() {
+            // Control Sequence: parameters until the final byte (from '@' to '~')
+            chars.next();
+            end += 1;
+            for (i, c) in chars.by_ref() {
+                end = i + c.len_utf8();
+                if ('@'..='~').contains(&c) {
+                    break;
+                }
+            }
+        } else if let Some((i, c)) = chars.next() {
+            end = i + c.len_utf8();
+        }
+        tokens.push(Token::Escape(&s[start..end]));
+    }
+    tokens
+}
+
+fn char_width(c: char) -> usize {
+    UnicodeWidthChar::width(c).unwrap_or(0)
+}
+
+fn tokens_width(tokens: &[Token]) -> usize {
+    tokens
+        .iter()
+        .map(|t| match t {
+            Token::Char(c) => char_width(*c),
+            Token::Escape(_) => 0,
+        })
+        .sum()
+}
+
+/// Display width of the widest line of the string, escape sequences excluded
+pub fn width(s: &str) -> usize {
+    tokenize(s)
+        .split(|t| matches!(t, Token::Char('\n')))
+        .map(tokens_width)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Wraps the string at word boundaries so that no line is wider than `width` columns
+///
+/// Words wider than `width` are split between two chars. Styles (SGR escape sequences) are
+/// closed at the end of each line and reopened at the beginning of the next one.
+pub fn wrap(s: &str, width: usize) -> String {
+    let width = cmp::max(width, 1);
+    let tokens = tokenize(s);
+    let mut lines = Vec::new();
+    for line in tokens.split(|t| matches!(t, Token::Char('\n'))) {
+        wrap_line(line, width, &mut lines);
+    }
+    render(&lines)
+}
+
+/// Truncates each line of the string wider than `width` columns and ends it with `marker`
+///
+/// The marker is dropped if it is wider than `width`. Escape sequences following the cut are
+/// kept so that styles are still closed.
+pub fn truncate(s: &str, width: usize, marker: &str) -> String {
+    let marker_tokens = tokenize(marker);
+    let marker_width = tokens_width(&marker_tokens);
+    let tokens = tokenize(s);
+    let mut lines = Vec::new();
+    for line in tokens.split(|t| matches!(t, Token::Char('\n'))) {
+        if tokens_width(line) <= width {
+            lines.push(line.to_vec());
+            continue;
+        }
+        let (marker_tokens, max) = if marker_width > width {
+            (&[][..], width)
+        } else {
+            (&marker_tokens[..], width - marker_width)
+        };
+        let mut truncated = Vec::with_capacity(line.len());
+        let mut line_width = 0;
+        let mut cut = false;
+        for token in line {
+            match token {
+                Token::Char(c) if !cut => {
+                    let w = char_width(*c);
+                    if line_width + w > max {
+                        truncated.extend_from_slice(marker_tokens);
+                        cut = true;
+                    } else {
+                        line_width += w;
+                        truncated.push(*token);
+                    }
+                }
+                Token::Char(_) => (),
+                Token::Escape(_) => truncated.push(*token),
+            }
+        }
+        lines.push(truncated);
+    }
+    render(&lines)
+}
+
+/// Replaces the columns of a single line string from `start` (as many as `insert` is wide)
+/// by `insert`
+///
+/// Styles active at the cuts are closed before `insert` and reopened after it.
+pub fn splice(s: &str, start: usize, insert: &str) -> String {
+    let end = start + width(insert);
+    let mut active: Vec<&str> = Vec::new();
+    let mut spliced = String::new();
+    let mut col = 0;
+    let mut inserted = false;
+    let mut reopen = false;
+    for token in tokenize(s) {
+        if col >= start && !inserted {
+            if !active.is_empty() {
+                spliced.push_str(RESET);
+            }
+            spliced.push_str(insert);
+            inserted = true;
+            reopen = true;
+        }
+        let kept = col < start || col >= end;
+        if kept && inserted && reopen {
+            spliced.push_str(&active.concat());
+            reopen = false;
+        }
+        match token {
+            Token::Escape(e) => {
+                if e == RESET || e == "\x1b[m" {
+                    active.clear();
+                } else if e.starts_with("\x1b[") && e.ends_with('m') {
+                    active.push(e);
+                }
+                if kept {
+                    spliced.push_str(e);
+                }
+            }
+            Token::Char(c) => {
+                if kept {
+                    spliced.push(c);
+                }
+                col += char_width(c);
+            }
+        }
+    }
+    if !inserted {
+        spliced.push_str(insert);
+    }
+    spliced
+}
+
+fn wrap_line<'a>(tokens: &[Token<'a>], width: usize, lines: &mut Vec<Vec<Token<'a>>>) {
+    let mut line = Vec::new();
+    let mut line_width = 0;
+    let mut first = true;
+    for word in tokens.split(|t| matches!(t, Token::Char(' '))) {
+        let word_width = tokens_width(word);
+        if !first {
+            if line_width + 1 + word_width <= width {
+                line.push(Token::Char(' '));
+                line.extend_from_slice(word);
+                line_width += 1 + word_width;
+                continue;
+            }
+            if word_width == 0 {
+                // spaces at a line break are dropped
+                line.extend_from_slice(word);
+                continue;
+            }
+            lines.push(mem::take(&mut line));
+            line_width = 0;
+        }
+        first = false;
+        // the word starts the line: split it if it is too wide
+        for token in word {
+            if let Token::Char(c) = token {
+                let w = char_width(*c);
+                if line_width > 0 && line_width + w > width {
+                    lines.push(mem::take(&mut line));
+                    line_width = 0;
+                }
+                line_width += w;
+            }
+            line.push(*token);
+        }
+    }
+    lines.push(line);
+}
+
+fn render(lines: &[Vec<Token>]) -> String {
+    let mut active: Vec<&str> = Vec::new();
+    let mut rendered = Vec::with_capacity(lines.len());
+    for line in lines {
+        let mut s = active.concat();
+        for token in line {
+            match token {
+                Token::Escape(e) => {
+                    s.push_str(e);
+                    if *e == RESET || *e == "\x1b[m" {
+                        active.clear();
+                    } else if e.starts_with("\x1b[") && e.ends_with('m') {
+                        active.push(e);
+                    }
+                }
+                Token::Char(c) => s.push(*c),
+            }
+        }
+        if !active.is_empty() {
+            s.push_str(RESET);
+        }
+        rendered.push(s);
+    }
+    rendered.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrap_words() {
+        assert_eq!(
+            "lorem ipsum\ndolor sit\namet",
+            wrap("lorem ipsum dolor sit amet", 11)
+        );
+        assert_eq!("foo\nbar\n\nbaz", wrap("foo bar\n\nbaz", 5));
+    }
+
+    #[test]
+    fn wrap_long_words() {
+        assert_eq!("a\nabcde\nfghij\nk b", wrap("a abcdefghijk b", 5));
+    }
+
+    #[test]
+    fn wrap_wide_characters() {
+        assert_eq!("スタ\nイル\nで", wrap("スタイルで", 5));
+        assert_eq!(4, width("スタ\nイル"));
+    }
+
+    #[test]
+    fn truncate_lines() {
+        assert_eq!("lorem…\nfoo", truncate("lorem ipsum\nfoo", 6, "…"));
+        assert_eq!("lor...", truncate("lorem ipsum", 6, "..."));
+        assert_eq!("lo", truncate("lorem ipsum", 2, "..."));
+        assert_eq!("スタ…", truncate("スタイルで", 6, "…"));
+    }
+
+    #[test]
+    fn truncate_ascii_escaped() {
+        assert_eq!(
+            "\x1b[1mbold \x1b[31mr…\x1b[0m",
+            truncate("\x1b[1mbold \x1b[31mred\x1b[0m text", 7, "…")
+        );
+    }
+
+    #[test]
+    fn splice_lines() {
+        assert_eq!("+- title -+---+", splice("+---------+---+", 2, " title "));
+        assert_eq!("ab xy", splice("ab", 2, " xy"));
+        assert_eq!(
+            "\x1b[1m═\x1b[0m title \x1b[1m═\x1b[0m",
+            splice("\x1b[1m═════════\x1b[0m", 1, " title ")
+        );
+    }
+
+    #[test]
+    fn wrap_ascii_escaped() {
+        let s = "plain \x1b[31mred text\x1b[0m end";
+        assert_eq!(
+            "plain \x1b[31mred\x1b[0m\n\x1b[31mtext\x1b[0m end",
+            wrap(s, 9)
+        );
+        assert_eq!(18, width(s));
+    }
+}